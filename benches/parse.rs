@@ -0,0 +1,47 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+/// Generates a spec with the given number of operator definitions, each
+/// with nested jlists and a short proof, like a large industrial model.
+fn large_spec(definitions: usize) -> String {
+    let mut code = String::from("---- MODULE Large ----\nEXTENDS Naturals, Sequences\n");
+    for i in 0..definitions {
+        code.push_str(&format!(
+            r#"(* Definition {i} *)
+VARIABLE v{i}
+Op{i}(a, b) ==
+  /\ a \in {{1, 2, 3}}
+  /\ \/ b = a + 1
+     \/ /\ b = a - 1
+        /\ v{i}' = [v{i} EXCEPT ![a] = b]
+  /\ LET t == [x \in 1..a |-> x * b] IN t[1] > 0
+  /\ IF a > b THEN <<a, b>> ELSE <<b, a>>
+THEOREM Thm{i} == \A x \in Nat : Op{i}(x, x + 1) => x < x + 1
+<1>1. TRUE
+  OBVIOUS
+<1> QED
+  BY <1>1 DEF Op{i}
+"#
+        ));
+    }
+    code.push_str("====\n");
+    code
+}
+
+fn parse(c: &mut Criterion) {
+    let code = large_spec(1000);
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_tlaplus::language())
+        .unwrap();
+    assert!(!parser.parse(&code, None).unwrap().root_node().has_error());
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(code.len() as u64));
+    group.sample_size(20);
+    group.bench_function("large_spec", |b| {
+        b.iter(|| parser.parse(&code, None).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
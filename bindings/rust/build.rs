@@ -1,3 +1,5 @@
+use std::fmt::Write;
+
 fn main() {
     let src_dir = std::path::Path::new("src");
 
@@ -16,4 +18,72 @@ fn main() {
     println!("cargo:rerun-if-changed={}", scanner_path.to_str().unwrap());
 
     c_config.compile("tree-sitter-tlaplus");
+
+    let node_types_path = src_dir.join("node-types.json");
+    println!(
+        "cargo:rerun-if-changed={}",
+        node_types_path.to_str().unwrap()
+    );
+    let node_types = std::fs::read_to_string(&node_types_path).unwrap();
+    let node_types: serde_json::Value = serde_json::from_str(&node_types).unwrap();
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    generate_node_kinds(&node_types, &out_dir);
+    generate_fields(&node_types, &out_dir);
+}
+
+/// Writes a static for every visible named node kind in node-types.json.
+fn generate_node_kinds(node_types: &serde_json::Value, out_dir: &std::path::Path) {
+    let mut kinds = Vec::new();
+    for node_type in node_types.as_array().unwrap() {
+        let kind = node_type["type"].as_str().unwrap();
+        if node_type["named"].as_bool().unwrap() && !kind.starts_with('_') {
+            kinds.push(kind);
+        }
+    }
+    kinds.sort_unstable();
+    kinds.dedup();
+
+    let mut code = String::new();
+    for kind in kinds {
+        writeln!(
+            code,
+            "pub static {}: NodeKind = NodeKind::new({:?});",
+            kind.to_uppercase(),
+            kind
+        )
+        .unwrap();
+    }
+
+    std::fs::write(out_dir.join("node_kinds.rs"), code).unwrap();
+}
+
+/// Writes a static for every field name in node-types.json, along with a
+/// list of all of them.
+fn generate_fields(node_types: &serde_json::Value, out_dir: &std::path::Path) {
+    let mut fields = Vec::new();
+    for node_type in node_types.as_array().unwrap() {
+        if let Some(node_fields) = node_type.get("fields").and_then(|f| f.as_object()) {
+            fields.extend(node_fields.keys().map(String::as_str));
+        }
+    }
+    fields.sort_unstable();
+    fields.dedup();
+
+    let mut code = String::new();
+    for field in &fields {
+        writeln!(
+            code,
+            "pub static {}: Field = Field::new({:?});",
+            field.to_uppercase(),
+            field
+        )
+        .unwrap();
+    }
+    writeln!(code, "static ALL: &[&Field] = &[").unwrap();
+    for field in &fields {
+        writeln!(code, "    &{},", field.to_uppercase()).unwrap();
+    }
+    writeln!(code, "];").unwrap();
+
+    std::fs::write(out_dir.join("fields.rs"), code).unwrap();
 }
@@ -0,0 +1,186 @@
+//! Reporting of syntax errors and omitted proofs found while parsing.
+
+use tree_sitter::{Node, Range, Tree};
+
+use crate::{node_kinds, walk::walk};
+
+/// The kind of problem reported by a [Diagnostic].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Text which could not be parsed.
+    Error,
+    /// A token the parser expected but did not find, and inserted to
+    /// recover.
+    Missing,
+}
+
+/// A syntax error in the parsed text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The range of the erroneous text; empty for missing tokens.
+    pub range: Range,
+    /// What kind of problem this is.
+    pub kind: ErrorKind,
+    /// A short human-readable description of the problem.
+    pub message: String,
+}
+
+/// The longest excerpt of erroneous text quoted in a message.
+const EXCERPT_LEN: usize = 20;
+
+/// The message for a module lacking its closing `====`.
+const UNTERMINATED_MODULE: &str = "unterminated module, missing ====";
+
+/// Get every syntax error in the tree in source order. Problems nested inside
+/// an erroneous region are not reported separately.
+pub fn diagnostics(tree: &Tree, src: &[u8]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    walk(tree.root_node(), |node| {
+        if node.is_error() {
+            diagnostics.push(Diagnostic {
+                range: node.range(),
+                kind: ErrorKind::Error,
+                message: error_message(node, src),
+            });
+        } else if node.is_missing() {
+            let message = if node.kind_id() == node_kinds::DOUBLE_LINE.id() {
+                UNTERMINATED_MODULE.to_string()
+            } else if node.is_named() {
+                format!("missing {}", node.kind())
+            } else {
+                format!("missing `{}`", node.kind())
+            };
+            diagnostics.push(Diagnostic {
+                range: node.range(),
+                kind: ErrorKind::Missing,
+                message,
+            });
+        }
+        !node.is_error()
+    });
+    diagnostics
+}
+
+/// Get the range of every proof left out with `OMITTED` in source order.
+/// These are not syntax errors, as the spec is well formed, but tools may
+/// want to flag the unfinished proofs.
+pub fn omitted_proofs(tree: &Tree) -> Vec<Range> {
+    let mut omitted = Vec::new();
+    walk(tree.root_node(), |node| {
+        if node.kind_id() == node_kinds::OMITTED.id() {
+            omitted.push(node.range());
+        }
+        !node.is_error()
+    });
+    omitted
+}
+
+fn error_message(node: Node, src: &[u8]) -> String {
+    let mut cursor = node.walk();
+    let mut children = node.children(&mut cursor);
+    let opens_module = children
+        .next()
+        .is_some_and(|child| child.kind_id() == node_kinds::SINGLE_LINE.id())
+        && children
+            .next()
+            .is_some_and(|child| child.kind() == "MODULE");
+    let mut cursor = node.walk();
+    let closes_module = node
+        .children(&mut cursor)
+        .any(|child| child.kind_id() == node_kinds::DOUBLE_LINE.id());
+    if opens_module && !closes_module {
+        return UNTERMINATED_MODULE.to_string();
+    }
+    let text = String::from_utf8_lossy(&src[node.byte_range()]);
+    let line = text.trim_start().lines().next().unwrap_or_default();
+    match line.char_indices().nth(EXCERPT_LEN) {
+        Some((end, _)) => format!("syntax error at `{}…`", &line[..end]),
+        None => format!("syntax error at `{line}`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_spec() {
+        let code = "---- MODULE Test ----\nop == 1\n====\n";
+        assert!(diagnostics(&crate::parse_with(code, true).unwrap(), code.as_bytes()).is_empty());
+    }
+
+    #[test]
+    fn test_unterminated_module() {
+        let code = "---- MODULE Test ----\nop == 1\n";
+        let diagnostics = diagnostics(&crate::parse_with(code, true).unwrap(), code.as_bytes());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, ErrorKind::Missing);
+        assert_eq!(diagnostics[0].message, "unterminated module, missing ====");
+        assert_eq!(diagnostics[0].range.start_point.row, 1);
+    }
+
+    #[test]
+    fn test_unterminated_module_before_another_module() {
+        let code = "---- MODULE A ----\nop == 1\n---- MODULE B ----\nop == 2\n====\n";
+        let tree = crate::parse_with(code, true).unwrap();
+        assert_eq!(tree.root_node().child(0).unwrap().kind(), "module");
+        let diagnostics = diagnostics(&tree, code.as_bytes());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, ErrorKind::Missing);
+        assert_eq!(diagnostics[0].message, "unterminated module, missing ====");
+    }
+
+    #[test]
+    fn test_missing_token() {
+        let code = "op == [a |-> 1\n";
+        let diagnostics = diagnostics(&crate::parse_with(code, true).unwrap(), code.as_bytes());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, ErrorKind::Missing);
+        assert_eq!(diagnostics[0].message, "missing `]`");
+        assert_eq!(diagnostics[0].range.start_byte, 14);
+        assert_eq!(diagnostics[0].range.end_byte, 14);
+    }
+
+    #[test]
+    fn test_unterminated_string() {
+        let code = "---- MODULE Test ----\nop == \"abc\nop2 == 1\n====\n";
+        let diagnostics = diagnostics(&crate::parse_with(code, true).unwrap(), code.as_bytes());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, ErrorKind::Missing);
+        assert_eq!(diagnostics[0].message, "missing `\"`");
+        assert_eq!(diagnostics[0].range.start_point.row, 1);
+    }
+
+    #[test]
+    fn test_unterminated_block_comment() {
+        let code = "---- MODULE A ----\n(* never closed\nop == 1\n====\n---- MODULE B ----\nop == 2\n====\n";
+        let diagnostics = diagnostics(&crate::parse_with(code, true).unwrap(), code.as_bytes());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, ErrorKind::Error);
+        assert_eq!(diagnostics[0].range.start_point.row, 1);
+        assert_eq!(diagnostics[0].range.end_point.row, 2);
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let code = "op == IF 1 THEN 2\n";
+        let diagnostics = diagnostics(&crate::parse_with(code, true).unwrap(), code.as_bytes());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, ErrorKind::Error);
+        assert_eq!(
+            diagnostics[0].message,
+            "syntax error at `op == IF 1 THEN 2`"
+        );
+    }
+
+    #[test]
+    fn test_omitted_proofs() {
+        let code = "---- MODULE Test ----\nTHEOREM TRUE OMITTED\nTHEOREM TRUE\n<1>1. TRUE OBVIOUS\n<1> QED PROOF OMITTED\n====\n";
+        let tree = crate::parse_with(code, true).unwrap();
+        assert!(diagnostics(&tree, code.as_bytes()).is_empty());
+        let omitted = omitted_proofs(&tree);
+        assert_eq!(omitted.len(), 2);
+        assert_eq!(omitted[0].start_point.row, 1);
+        assert_eq!(omitted[1].start_point.row, 4);
+    }
+}
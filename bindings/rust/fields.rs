@@ -0,0 +1,100 @@
+//! Ids of the field names in this grammar, for use with
+//! [`Node::child_by_field_id`][] instead of looking up names on every call.
+//!
+//! ```
+//! use tree_sitter_tlaplus::fields;
+//!
+//! let mut parser = tree_sitter::Parser::new();
+//! parser.set_language(&tree_sitter_tlaplus::language()).unwrap();
+//! let code = "---- MODULE Test ----\n====\n";
+//! let tree = parser.parse(code, None).unwrap();
+//! let module = tree.root_node().named_child(0).unwrap();
+//! let name = module.child_by_field_id(fields::NAME.id()).unwrap();
+//! assert_eq!(name.utf8_text(code.as_bytes()).unwrap(), "Test");
+//! ```
+//!
+//! [`Node::child_by_field_id`]: https://docs.rs/tree-sitter/*/tree_sitter/struct.Node.html#method.child_by_field_id
+
+use std::sync::OnceLock;
+
+/// A field whose id is looked up in the [language][] on first use.
+///
+/// [language]: crate::language
+pub struct Field {
+    name: &'static str,
+    id: OnceLock<u16>,
+}
+
+impl Field {
+    const fn new(name: &'static str) -> Self {
+        Field {
+            name,
+            id: OnceLock::new(),
+        }
+    }
+
+    /// The name of this field, as used in queries and
+    /// [`Node::child_by_field_name`][].
+    ///
+    /// [`Node::child_by_field_name`]: https://docs.rs/tree-sitter/*/tree_sitter/struct.Node.html#method.child_by_field_name
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The id of this field, as accepted by [`Node::child_by_field_id`][].
+    ///
+    /// [`Node::child_by_field_id`]: https://docs.rs/tree-sitter/*/tree_sitter/struct.Node.html#method.child_by_field_id
+    pub fn id(&self) -> u16 {
+        *self.id.get_or_init(|| {
+            crate::language()
+                .field_id_for_name(self.name)
+                .map_or(0, |id| id.get())
+        })
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/fields.rs"));
+
+/// Get the id of the field with the given name, if this grammar has one.
+pub fn field_id(name: &str) -> Option<u16> {
+    ALL.iter()
+        .find(|field| field.name == name)
+        .map(|field| field.id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_ids() {
+        assert_eq!(field_id("name"), Some(NAME.id()));
+        assert_eq!(field_id("definition"), Some(DEFINITION.id()));
+        assert_eq!(field_id("no_such_field"), None);
+        for field in ALL {
+            assert_ne!(field.id(), 0, "{} has no id", field.name());
+        }
+
+        let code = "---- MODULE Test ----\nop(a, b) == a\n====\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&crate::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let def = tree
+            .root_node()
+            .named_child(0)
+            .unwrap()
+            .named_child(3)
+            .unwrap();
+        let name = def.child_by_field_id(NAME.id()).unwrap();
+        assert_eq!(name.utf8_text(code.as_bytes()).unwrap(), "op");
+        let params: Vec<_> = def
+            .children_by_field_id(
+                std::num::NonZeroU16::new(PARAMETER.id()).unwrap(),
+                &mut def.walk(),
+            )
+            .filter(|param| param.is_named())
+            .map(|param| param.utf8_text(code.as_bytes()).unwrap())
+            .collect();
+        assert_eq!(params, ["a", "b"]);
+    }
+}
@@ -0,0 +1,146 @@
+//! Serialization of parse trees to JSON.
+
+use serde_json::{json, Map, Value};
+use tree_sitter::{Point, Tree, TreeCursor};
+
+/// Convert the tree to a JSON value mirroring its structure. Each node is an
+/// object with `kind`, `field`, `start`, `end`, and `children` keys, where the
+/// positions hold the `byte`, `row`, and `column` of the node boundaries.
+/// Leaf nodes also have a `text` key holding their source text.
+///
+/// Anonymous nodes such as keywords and punctuation are skipped; use
+/// [to_sexp_json_with] to include them.
+pub fn to_sexp_json(tree: &Tree, src: &[u8]) -> Value {
+    to_sexp_json_with(tree, src, false)
+}
+
+/// Convert the tree to a JSON value as with [to_sexp_json], optionally
+/// including anonymous nodes.
+pub fn to_sexp_json_with(tree: &Tree, src: &[u8], include_anonymous: bool) -> Value {
+    node_to_json(&mut tree.walk(), src, include_anonymous)
+}
+
+/// Builds the JSON value of the node at the cursor. The walk keeps its own
+/// stack of the objects still open, so deeply nested trees do not overflow
+/// the call stack.
+fn node_to_json(cursor: &mut TreeCursor, src: &[u8], include_anonymous: bool) -> Value {
+    let mut open = vec![(node_object(cursor, src), Vec::new())];
+    // Whether the cursor is on the innermost open node, with none of its
+    // children visited yet.
+    let mut entered = true;
+    loop {
+        let moved = if entered {
+            cursor.goto_first_child()
+        } else {
+            cursor.goto_next_sibling()
+        };
+        if moved {
+            entered = include_anonymous || cursor.node().is_named();
+            if entered {
+                open.push((node_object(cursor, src), Vec::new()));
+            }
+            continue;
+        }
+        if !entered {
+            cursor.goto_parent();
+        }
+        entered = false;
+        let (mut object, children) = open.pop().unwrap();
+        object.insert("children".into(), children.into());
+        match open.last_mut() {
+            Some((_, siblings)) => siblings.push(Value::Object(object)),
+            None => return Value::Object(object),
+        }
+    }
+}
+
+/// Builds the JSON object of the node at the cursor, without its children.
+fn node_object(cursor: &TreeCursor, src: &[u8]) -> Map<String, Value> {
+    let node = cursor.node();
+    let mut object = Map::new();
+    object.insert("kind".into(), node.kind().into());
+    object.insert("field".into(), cursor.field_name().into());
+    object.insert(
+        "start".into(),
+        position(node.start_byte(), node.start_position()),
+    );
+    object.insert("end".into(), position(node.end_byte(), node.end_position()));
+    if node.child_count() == 0 {
+        let text = String::from_utf8_lossy(&src[node.byte_range()]);
+        object.insert("text".into(), text.into());
+    }
+    object
+}
+
+fn position(byte: usize, point: Point) -> Value {
+    json!({ "byte": byte, "row": point.row, "column": point.column })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Strips positions, leaving only the shape of the tree.
+    fn shape(value: &Value) -> Value {
+        let children: Vec<_> = value["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(shape)
+            .collect();
+        json!({ "kind": value["kind"], "field": value["field"], "children": children })
+    }
+
+    #[test]
+    fn test_named_nodes() {
+        let src = "op(x) == x";
+        let value = to_sexp_json(&crate::parse_with(src, true).unwrap(), src.as_bytes());
+        assert_eq!(
+            shape(&value),
+            json!({ "kind": "source_file", "field": null, "children": [
+                { "kind": "operator_definition", "field": null, "children": [
+                    { "kind": "identifier", "field": "name", "children": [] },
+                    { "kind": "identifier", "field": "parameter", "children": [] },
+                    { "kind": "def_eq", "field": null, "children": [] },
+                    { "kind": "identifier_ref", "field": "definition", "children": [] },
+                ]},
+            ]})
+        );
+        let definition = &value["children"][0]["children"][3];
+        assert_eq!(definition["text"], "x");
+        assert_eq!(
+            definition["start"],
+            json!({ "byte": 9, "row": 0, "column": 9 })
+        );
+        assert_eq!(
+            definition["end"],
+            json!({ "byte": 10, "row": 0, "column": 10 })
+        );
+    }
+
+    #[test]
+    fn test_anonymous_nodes() {
+        let src = "op(x) == x";
+        let value = to_sexp_json_with(&crate::parse_with(src, true).unwrap(), src.as_bytes(), true);
+        let kinds: Vec<_> = value["children"][0]["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|child| child["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "identifier",
+                "(",
+                "identifier",
+                ")",
+                "def_eq",
+                "identifier_ref"
+            ]
+        );
+        let def_eq = &value["children"][0]["children"][4];
+        assert_eq!(def_eq["children"][0]["kind"], "==");
+        assert_eq!(def_eq["children"][0]["text"], "==");
+    }
+}
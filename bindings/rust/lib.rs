@@ -5,6 +5,9 @@
 //!
 //! ```
 //! let code = r#"
+//! ---- MODULE Test ----
+//! op == 1
+//! ====
 //! "#;
 //! let mut parser = tree_sitter::Parser::new();
 //! parser.set_language(&tree_sitter_tlaplus::language()).expect("Error loading Tlaplus grammar");
@@ -19,6 +22,33 @@
 
 use tree_sitter::Language;
 
+mod diagnostics;
+pub mod fields;
+#[cfg(feature = "serde")]
+mod json;
+mod locals;
+mod lsp;
+pub mod node_kinds;
+mod outline;
+mod parse;
+mod proofs;
+mod symbols;
+mod walk;
+
+pub use diagnostics::{diagnostics, omitted_proofs, Diagnostic, ErrorKind};
+#[cfg(feature = "serde")]
+pub use json::{to_sexp_json, to_sexp_json_with};
+pub use locals::{references, resolve_local};
+pub use lsp::{byte_to_lsp_position, node_lsp_range};
+pub use outline::{
+    actions, declarations, definitions, extends, instances, module_name, Decl, Declarations,
+    DefKind, Definition, Instance,
+};
+pub use parse::{parse, parse_expression, parse_with, ParseError};
+pub use proofs::{proof_steps, theorems, NamedUnit, ProofStep, StepKind, UnitKind};
+pub use symbols::{symbol_style, to_ascii, to_unicode, SymbolStyle};
+pub use walk::{named_children, named_descendants};
+
 extern "C" {
     fn tree_sitter_tlaplus() -> Language;
 }
@@ -30,17 +60,48 @@ pub fn language() -> Language {
     unsafe { tree_sitter_tlaplus() }
 }
 
+/// Get the ABI version of the generated parser, without constructing a
+/// [Parser][].
+///
+/// [Parser]: https://docs.rs/tree-sitter/*/tree_sitter/struct.Parser.html
+pub fn abi_version() -> usize {
+    language().version()
+}
+
+/// The version of this grammar crate.
+pub const GRAMMAR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 /// The content of the [`node-types.json`][] file for this grammar.
 ///
 /// [`node-types.json`]: https://tree-sitter.github.io/tree-sitter/using-parsers#static-node-types
 pub const NODE_TYPES: &str = include_str!("../../src/node-types.json");
 
-// Uncomment these to include any queries that this grammar contains
-
 pub const HIGHLIGHTS_QUERY: &str = include_str!("../../queries/highlights.scm");
-// pub const INJECTIONS_QUERY: &str = include_str!("../../queries/injections.scm");
+pub const INJECTIONS_QUERY: &str = include_str!("../../queries/injections.scm");
 pub const LOCALS_QUERY: &str = include_str!("../../queries/locals.scm");
-// pub const TAGS_QUERY: &str = include_str!("../../queries/tags.scm");
+pub const TAGS_QUERY: &str = include_str!("../../queries/tags.scm");
+pub const FOLDS_QUERY: &str = include_str!("../../queries/folds.scm");
+pub const INDENTS_QUERY: &str = include_str!("../../queries/indents.scm");
+
+/// Get a [HighlightConfiguration][] for this grammar, assembled from the
+/// highlights, injections, and locals queries and configured with the given
+/// highlight names.
+///
+/// [HighlightConfiguration]: https://docs.rs/tree-sitter-highlight/*/tree_sitter_highlight/struct.HighlightConfiguration.html
+#[cfg(feature = "highlight")]
+pub fn highlight_configuration(
+    recognized_names: &[&str],
+) -> Result<tree_sitter_highlight::HighlightConfiguration, tree_sitter::QueryError> {
+    let mut config = tree_sitter_highlight::HighlightConfiguration::new(
+        language(),
+        "tlaplus",
+        HIGHLIGHTS_QUERY,
+        INJECTIONS_QUERY,
+        LOCALS_QUERY,
+    )?;
+    config.configure(recognized_names);
+    Ok(config)
+}
 
 #[cfg(test)]
 mod tests {
@@ -51,4 +112,783 @@ mod tests {
             .set_language(&super::language())
             .expect("Error loading Tlaplus grammar");
     }
+
+    #[test]
+    fn test_abi_version() {
+        let supported =
+            tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION..=tree_sitter::LANGUAGE_VERSION;
+        assert!(supported.contains(&super::abi_version()));
+        assert!(!super::GRAMMAR_VERSION.is_empty());
+    }
+
+    #[test]
+    fn test_tags_query() {
+        let config = tree_sitter_tags::TagsConfiguration::new(
+            super::language(),
+            super::TAGS_QUERY,
+            super::LOCALS_QUERY,
+        )
+        .expect("Error loading tags query");
+        let code = br#"
+---- MODULE Test ----
+CONSTANT N
+max(a, b) == IF a > b THEN a ELSE b
+THEOREM MaxIsN == max(N, N) = N
+====
+"#;
+        let mut context = tree_sitter_tags::TagsContext::new();
+        let (tags, _) = context
+            .generate_tags(&config, code, None)
+            .expect("Error generating tags");
+        let tags: Vec<_> = tags
+            .map(|tag| {
+                let tag = tag.unwrap();
+                (
+                    config.syntax_type_name(tag.syntax_type_id),
+                    tag.is_definition,
+                    std::str::from_utf8(&code[tag.name_range.clone()]).unwrap(),
+                    tag.span.start.row,
+                )
+            })
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                ("module", true, "Test", 1),
+                ("constant", true, "N", 2),
+                ("operator", true, "max", 3),
+                ("theorem", true, "MaxIsN", 4),
+                ("call", false, "max", 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_folds_query() {
+        let code = r#"
+---- MODULE Test ----
+THEOREM TRUE
+<1>1. TRUE
+  OBVIOUS
+<1> QED
+  BY <1>1
+====
+"#;
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let query = tree_sitter::Query::new(&super::language(), super::FOLDS_QUERY)
+            .expect("Error loading folds query");
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let folds: Vec<_> = cursor
+            .captures(&query, tree.root_node(), code.as_bytes())
+            .map(|(m, i)| {
+                let node = m.captures[i].node;
+                (
+                    node.kind(),
+                    node.start_position().row,
+                    node.end_position().row,
+                )
+            })
+            .collect();
+        assert_eq!(folds, vec![("module", 1, 7), ("non_terminal_proof", 3, 6)]);
+    }
+
+    #[test]
+    fn test_injections_query() {
+        let code = r#"
+---- MODULE Test ----
+(* Holds when $x \in S$ *)
+op == 1
+(* Plain text *)
+op2 == 2
+(* --algorithm A
+begin skip;
+end algorithm; *)
+====
+"#;
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let query = tree_sitter::Query::new(&super::language(), super::INJECTIONS_QUERY)
+            .expect("Error loading injections query");
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let injections: Vec<_> = cursor
+            .matches(&query, tree.root_node(), code.as_bytes())
+            .map(|m| {
+                let language = query.property_settings(m.pattern_index)[0]
+                    .value
+                    .as_deref()
+                    .unwrap();
+                let text = m.captures[0].node.utf8_text(code.as_bytes()).unwrap();
+                (language, text.trim().lines().next().unwrap())
+            })
+            .collect();
+        assert_eq!(
+            injections,
+            vec![
+                ("latex", "Holds when $x \\in S$"),
+                ("comment", "Plain text"),
+                ("comment", ";"),
+            ]
+        );
+    }
+
+    /// Returns the text of each `@local.definition` capture together with the
+    /// kind of its innermost enclosing `@local.scope`.
+    fn local_definitions(code: &str) -> Vec<(String, &'static str)> {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let query = tree_sitter::Query::new(&super::language(), super::LOCALS_QUERY)
+            .expect("Error loading locals query");
+        let names = query.capture_names();
+        let mut scopes = Vec::new();
+        let mut definitions = Vec::new();
+        let mut cursor = tree_sitter::QueryCursor::new();
+        for (m, i) in cursor.captures(&query, tree.root_node(), code.as_bytes()) {
+            let node = m.captures[i].node;
+            match names[m.captures[i].index as usize] {
+                "local.scope" => scopes.push(node),
+                "local.definition" => definitions.push(node),
+                _ => {}
+            }
+        }
+        definitions
+            .into_iter()
+            .map(|def| {
+                let scope = scopes
+                    .iter()
+                    .filter(|s| s.byte_range().contains(&def.start_byte()))
+                    .min_by_key(|s| s.byte_range().len())
+                    .unwrap();
+                (
+                    def.utf8_text(code.as_bytes()).unwrap().to_owned(),
+                    scope.kind(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_locals_query_quantifiers() {
+        let code = "---- MODULE Test ----\nop == \\E x : \\A y \\in S : P(x, y)\n====\n";
+        let defs = local_definitions(code);
+        assert!(defs.contains(&("x".to_owned(), "unbounded_quantification")));
+        assert!(defs.contains(&("y".to_owned(), "bounded_quantification")));
+    }
+
+    #[test]
+    fn test_locals_query_function_domain() {
+        let code = "---- MODULE Test ----\nPos(f) == \\A x \\in DOMAIN f : f[x] > 0\n====\n";
+        let defs = local_definitions(code);
+        assert!(defs.contains(&("f".to_owned(), "operator_definition")));
+        assert!(defs.contains(&("x".to_owned(), "bounded_quantification")));
+    }
+
+    #[test]
+    fn test_locals_query_choose() {
+        let code = "---- MODULE Test ----\nop == CHOOSE <<a, b>> \\in S : a # b\n====\n";
+        let defs = local_definitions(code);
+        assert!(defs.contains(&("a".to_owned(), "choose")));
+        assert!(defs.contains(&("b".to_owned(), "choose")));
+    }
+
+    #[test]
+    fn test_locals_query_set_builders() {
+        let code = "---- MODULE Test ----\nop == {x \\in S : x > 0} \\cup {y + z : y \\in S, z \\in T}\n====\n";
+        let defs = local_definitions(code);
+        assert!(defs.contains(&("x".to_owned(), "set_filter")));
+        assert!(defs.contains(&("y".to_owned(), "set_map")));
+        assert!(defs.contains(&("z".to_owned(), "set_map")));
+    }
+
+    #[test]
+    fn test_locals_query_function_literal() {
+        let code = "---- MODULE Test ----\nop == [x \\in S, <<y, z>> \\in T |-> x + y + z]\n====\n";
+        let defs = local_definitions(code);
+        for name in ["x", "y", "z"] {
+            assert!(defs.contains(&(name.to_owned(), "function_literal")));
+        }
+    }
+
+    /// Returns the text of each node captured by the highlights query together
+    /// with its capture name, keeping only the earliest pattern per node.
+    fn highlight_captures(code: &str) -> Vec<(&str, String)> {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let query = tree_sitter::Query::new(&super::language(), super::HIGHLIGHTS_QUERY)
+            .expect("Error loading highlights query");
+        let names = query.capture_names();
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut captures: Vec<_> = cursor
+            .matches(&query, tree.root_node(), code.as_bytes())
+            .flat_map(|m| {
+                m.captures
+                    .iter()
+                    .map(move |c| (c.node, m.pattern_index, c.index))
+            })
+            .collect();
+        // Earlier patterns take precedence, so keep the first capture per node.
+        captures.sort_by_key(|&(node, pattern, _)| (node.id(), pattern));
+        captures.dedup_by_key(|&mut (node, _, _)| node.id());
+        captures
+            .into_iter()
+            .map(|(node, _, index)| {
+                (
+                    node.utf8_text(code.as_bytes()).unwrap(),
+                    names[index as usize].to_owned(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_locals_query_let_in() {
+        let code = r#"
+---- MODULE Test ----
+op ==
+  LET Double(x) == 2 * x
+      fact[n \in Nat] == IF n = 0 THEN 1 ELSE n * fact[n - 1]
+  IN Double(fact[3])
+====
+"#;
+        let defs = local_definitions(code);
+        assert!(defs.contains(&("Double".to_owned(), "operator_definition")));
+        assert!(defs.contains(&("x".to_owned(), "operator_definition")));
+        assert!(defs.contains(&("fact".to_owned(), "function_definition")));
+        assert!(defs.contains(&("n".to_owned(), "function_definition")));
+    }
+
+    #[test]
+    fn test_highlights_query_record_fields() {
+        let code = r#"
+---- MODULE Test ----
+S == [a : Nat]
+r == [b |-> 1]
+v == r.c
+u == [r EXCEPT !.d = 2]
+====
+"#;
+        let attributes: Vec<_> = highlight_captures(code)
+            .into_iter()
+            .filter(|(_, name)| name == "attribute")
+            .map(|(text, _)| text)
+            .collect();
+        for field in ["a", "b", "c", "d"] {
+            assert!(attributes.contains(&field), "{field}");
+        }
+        assert!(!attributes.contains(&"r"));
+    }
+
+    #[test]
+    fn test_highlights_query_temporal_operators() {
+        let code = r#"
+---- MODULE Test ----
+Spec == [](P => <>Q) /\ (P ~> Q) /\ (A -+-> B)
+====
+"#;
+        let captures = highlight_captures(code);
+        for op in ["[]", "<>", "~>", "-+->"] {
+            assert!(captures.contains(&(op, "operator".to_owned())), "{op}");
+        }
+    }
+
+    #[test]
+    fn test_highlights_query_tlc_operators() {
+        let code = "---- MODULE Test ----\nf == 1 :> 2 @@ 3 :> 4\n====\n";
+        let captures = highlight_captures(code);
+        assert!(captures.contains(&(":>", "operator".to_owned())));
+        assert!(captures.contains(&("@@", "operator".to_owned())));
+    }
+
+    #[test]
+    fn test_highlights_query_axiom() {
+        let code = "---- MODULE Test ----\nASSUME TRUE\nAXIOM Ax == TRUE\n====\n";
+        let captures = highlight_captures(code);
+        assert!(captures.contains(&("ASSUME", "keyword".to_owned())));
+        assert!(captures.contains(&("AXIOM", "keyword.axiom".to_owned())));
+    }
+
+    #[test]
+    fn test_highlights_query_proof_steps() {
+        let code = r#"
+---- MODULE Test ----
+THEOREM TRUE
+<1>1. TRUE
+<1>2. QED BY <1>1
+====
+"#;
+        let captures = highlight_captures(code);
+        assert!(captures.contains(&("<1>1.", "label".to_owned())));
+        assert!(captures.contains(&("<1>1", "label".to_owned())));
+        assert!(captures.contains(&("<", "punctuation.bracket".to_owned())));
+        assert!(!captures.iter().any(|(_, name)| name == "tag"));
+    }
+
+    #[test]
+    fn test_highlights_query_keywords() {
+        let code = r#"
+---- MODULE Test ----
+EXTENDS Naturals
+RECURSIVE F(_)
+F(n) == IF n = 0 THEN 0 ELSE F(n - 1)
+LOCAL L == INSTANCE M WITH a <- 1
+Op == /\ CHOOSE y \in SUBSET UNION {DOMAIN x} : TRUE
+      /\ ENABLED UNCHANGED x
+      /\ [x EXCEPT ![1] = 2]
+      /\ CASE x = 1 -> 1 [] OTHER -> 2
+      /\ LET G == 1 IN F(LAMBDA z : z)
+THEOREM T == ASSUME NEW c PROVE c
+PROOF
+<1>1. SUFFICES TRUE
+  OBVIOUS
+<1>2. HAVE TRUE
+<1>3. TAKE t
+<1>4. PICK p : TRUE
+  BY ONLY <1>1 DEF F
+<1>5. WITNESS 1
+<1>6. CASE TRUE
+  OMITTED
+<1>7. USE DEFS F
+<1>8. HIDE F
+<1> QED
+(* --algorithm A
+begin
+  while TRUE do
+    with v = 1 do skip end with
+  end while
+end algorithm *)
+====
+"#;
+        let keywords: Vec<_> = highlight_captures(code)
+            .into_iter()
+            .filter(|(_, name)| name.starts_with("keyword"))
+            .map(|(text, _)| text)
+            .collect();
+        let expected =
+            "CHOOSE ENABLED UNCHANGED DOMAIN SUBSET UNION EXCEPT OTHER LAMBDA RECURSIVE \
+            INSTANCE WITH LOCAL ASSUME NEW PROVE PROOF SUFFICES OBVIOUS HAVE TAKE PICK BY ONLY \
+            DEF WITNESS CASE OMITTED USE DEFS HIDE QED while do with end";
+        for keyword in expected.split_whitespace() {
+            assert!(keywords.contains(&keyword), "{keyword}");
+        }
+        assert_eq!(keywords.iter().filter(|&&k| k == "end").count(), 3);
+    }
+
+    #[test]
+    fn test_highlights_query_comment_annotations() {
+        let code = r#"
+---- MODULE Test ----
+\* @type: Int;
+VARIABLE x
+\* plain @type: comment
+Init == x = 0
+
+(* PlusCal options (-termination) *)
+====
+"#;
+        let captures = highlight_captures(code);
+        assert!(captures.contains(&("\\* @type: Int;", "attribute".to_owned())));
+        assert!(captures.contains(&("\\* plain @type: comment", "comment".to_owned())));
+        assert!(captures.contains(&("PlusCal options (-termination)", "attribute".to_owned())));
+    }
+
+    #[test]
+    fn test_highlights_query_translation_markers() {
+        let code = r#"
+---- MODULE Test ----
+\* BEGIN TRANSLATION (chksum(pcal) = "0")
+VARIABLE pc
+\* END TRANSLATION
+====
+"#;
+        let captures = highlight_captures(code);
+        let start = "\\* BEGIN TRANSLATION (chksum(pcal) = \"0\")";
+        assert!(captures.contains(&(start, "comment".to_owned())));
+        assert!(captures.contains(&("\\* END TRANSLATION", "comment".to_owned())));
+    }
+
+    /// Every query this crate ships, by file name.
+    const QUERIES: &[(&str, &str)] = &[
+        ("folds", super::FOLDS_QUERY),
+        ("highlights", super::HIGHLIGHTS_QUERY),
+        ("indents", super::INDENTS_QUERY),
+        ("injections", super::INJECTIONS_QUERY),
+        ("locals", super::LOCALS_QUERY),
+        ("tags", super::TAGS_QUERY),
+    ];
+
+    /// Load a query, describing an error by its position in the query file
+    /// and the offending node kind, field or capture.
+    fn load_query(name: &str, source: &str) -> Result<tree_sitter::Query, String> {
+        tree_sitter::Query::new(&super::language(), source).map_err(|error| {
+            let line = source.lines().nth(error.row).unwrap_or_default();
+            format!(
+                "queries/{name}.scm:{}:{}: {:?} error on `{}`\n{line}",
+                error.row + 1,
+                error.column + 1,
+                error.kind,
+                error.message,
+            )
+        })
+    }
+
+    #[test]
+    fn test_can_load_all_queries() {
+        for path in std::fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/queries")).unwrap() {
+            let path = path.unwrap().path();
+            let name = path.file_stem().unwrap().to_str().unwrap();
+            assert!(
+                QUERIES.iter().any(|&(query, _)| query == name),
+                "queries/{name}.scm has no query constant"
+            );
+        }
+        for &(name, source) in QUERIES {
+            if let Err(error) = load_query(name, source) {
+                panic!("{error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_query_reports_invalid_node_kind() {
+        let error = load_query("broken", "(comment) @comment\n(no_such_node) @x\n").unwrap_err();
+        assert!(error.starts_with("queries/broken.scm:2:"), "{error}");
+        assert!(error.contains("NodeType"), "{error}");
+        assert!(error.contains("no_such_node"), "{error}");
+    }
+
+    /// A spec with nested jlists of 16 items, each holding a set of `size`.
+    fn large_jlist_spec(size: usize) -> String {
+        let mut code = String::from("---- MODULE Test ----\nop ==\n");
+        for i in 0..16 {
+            let indent = 2 + 3 * (i % 8);
+            let set = (0..size).map(|j| j.to_string()).collect::<Vec<_>>();
+            code.push_str(&format!(
+                "{:indent$}/\\ x{i} \\in {{{}}}\n",
+                "",
+                set.join(", ")
+            ));
+        }
+        code.push_str("====\n");
+        code
+    }
+
+    fn min_parse_time(code: &str) -> std::time::Duration {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        (0..3)
+            .map(|_| {
+                let start = std::time::Instant::now();
+                let tree = parser.parse(code, None).unwrap();
+                let elapsed = start.elapsed();
+                assert!(!tree.root_node().has_error());
+                elapsed
+            })
+            .min()
+            .unwrap()
+    }
+
+    // Timing ratios are too noisy for a default run; use
+    // `cargo test --release -- --ignored test_parse_time_is_linear`.
+    #[test]
+    #[ignore]
+    fn test_parse_time_is_linear() {
+        let small = large_jlist_spec(500);
+        let large = large_jlist_spec(4000);
+        let ratio = (large.len() as f64) / (small.len() as f64);
+        let elapsed_ratio =
+            min_parse_time(&large).as_secs_f64() / min_parse_time(&small).as_secs_f64();
+        // Quadratic behaviour would be around ratio²; allow noise above ratio.
+        assert!(elapsed_ratio < 2.0 * ratio, "{elapsed_ratio} vs {ratio}");
+    }
+
+    fn point_at(code: &str, byte: usize) -> tree_sitter::Point {
+        let before = &code[..byte];
+        let row = before.matches('\n').count();
+        let column = byte - before.rfind('\n').map_or(0, |i| i + 1);
+        tree_sitter::Point { row, column }
+    }
+
+    /// Replaces `start..end` of `code` with `text`, then checks that
+    /// reusing the old tree gives the same result as a fresh parse.
+    fn assert_incremental_reparse(code: &str, start: usize, end: usize, text: &str) {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        let mut tree = parser.parse(code, None).unwrap();
+        let edited = format!("{}{}{}", &code[..start], text, &code[end..]);
+        tree.edit(&tree_sitter::InputEdit {
+            start_byte: start,
+            old_end_byte: end,
+            new_end_byte: start + text.len(),
+            start_position: point_at(code, start),
+            old_end_position: point_at(code, end),
+            new_end_position: point_at(&edited, start + text.len()),
+        });
+        let reparsed = parser.parse(&edited, Some(&tree)).unwrap();
+        let fresh = parser.parse(&edited, None).unwrap();
+        assert_eq!(
+            reparsed.root_node().to_sexp(),
+            fresh.root_node().to_sexp(),
+            "{edited}"
+        );
+    }
+
+    #[test]
+    fn test_incremental_reparse_of_jlist_indentation() {
+        let code = "---- MODULE Test ----
+op ==
+  /\\ a
+  /\\ \\/ b
+     \\/ /\\ c
+        /\\ d
+  /\\ e
+====
+";
+        let line_starts = code.match_indices('\n').map(|(i, _)| i + 1);
+        for start in line_starts.filter(|&i| code[i..].starts_with(' ')) {
+            assert_incremental_reparse(code, start, start, "   ");
+            assert_incremental_reparse(code, start, start + 2, "");
+            assert_incremental_reparse(code, start, start + 1, "");
+        }
+    }
+
+    #[test]
+    fn test_incremental_reparse_of_tab_indentation() {
+        let code = "---- MODULE Test ----
+op ==\t/\\ a
+\t/\\ \\/ b
+\t   \\/ /\\ c
+\t\t/\\ d
+\t/\\ e
+====
+";
+        let line_starts = code.match_indices('\n').map(|(i, _)| i + 1);
+        for start in line_starts.filter(|&i| code[i..].starts_with('\t')) {
+            assert_incremental_reparse(code, start, start, "\t");
+            assert_incremental_reparse(code, start, start + 1, "");
+            assert_incremental_reparse(code, start, start + 1, "        ");
+            assert_incremental_reparse(code, start, start + 1, " ");
+        }
+        let mid_line_tab = code.find("==\t").unwrap() + 2;
+        assert_incremental_reparse(code, mid_line_tab, mid_line_tab + 1, " ");
+        assert_incremental_reparse(code, mid_line_tab, mid_line_tab, "\t");
+    }
+
+    #[test]
+    fn test_incremental_reparse_of_terminated_jlists() {
+        let code = "
+---- MODULE Test ----
+op == (
+  \\/ 1
+  \\/  \\/ 2
+      \\/ 3
+       )
+  \\/ 4
+====
+";
+        assert_incremental_reparse(code, 37, 38, "  ");
+        let code = "---- MODULE Test ----
+op ==
+  IF c
+  THEN /\\ a
+       /\\ \\/ b
+          \\/ c
+  ELSE /\\ d
+       /\\ e
+next == 1
+====
+";
+        assert_incremental_reparse(code, 34, 37, " ");
+    }
+
+    #[test]
+    fn test_adjacent_block_comment_delimiters() {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        for comment in ["(*(**)*)", "(* a (* b *) c *)", "(* (* *) *)", "(* (* *)*)"] {
+            let code = format!("---- MODULE Test ----\n{comment}\nop == 1\n====\n");
+            let tree = parser.parse(&code, None).unwrap();
+            assert!(!tree.root_node().has_error(), "{comment}");
+            let module = tree.root_node().child(0).unwrap();
+            let mut cursor = module.walk();
+            let comments: Vec<_> = module
+                .children(&mut cursor)
+                .filter(|node| node.kind() == "block_comment")
+                .map(|node| node.byte_range())
+                .collect();
+            let start = code.find(comment).unwrap();
+            assert_eq!(comments, vec![start..start + comment.len()], "{comment}");
+        }
+    }
+
+    #[test]
+    fn test_deeply_nested_jlist_fits_scanner_state() {
+        let code = format!(
+            "---- MODULE Test ----\nop == {}x\n====\n",
+            "/\\ ".repeat(1000)
+        );
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        let tree = parser.parse(&code, None).unwrap();
+        assert!(tree.root_node().has_error());
+        assert_incremental_reparse(&code, 30, 30, " ");
+    }
+
+    #[test]
+    fn test_jlist_nesting_limit() {
+        let nested = |depth| {
+            format!(
+                "---- MODULE Test ----\nop == {}x\n====\n",
+                "/\\ ".repeat(depth)
+            )
+        };
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        let tree = parser.parse(nested(256), None).unwrap();
+        assert!(!tree.root_node().has_error());
+        let tree = parser.parse(nested(257), None).unwrap();
+        assert!(tree.root_node().has_error());
+    }
+
+    #[test]
+    fn test_deeply_nested_parentheses() {
+        let depth = 100_000;
+        let code = format!(
+            "---- MODULE Test ----\nop == {}x{}\n====\n",
+            "(".repeat(depth),
+            ")".repeat(depth)
+        );
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        let tree = parser.parse(&code, None).unwrap();
+        assert!(!tree.root_node().has_error());
+        assert!(super::diagnostics(&tree, code.as_bytes()).is_empty());
+        assert!(super::proof_steps(&tree, code.as_bytes()).is_empty());
+        #[cfg(feature = "serde")]
+        {
+            // Take the value apart node by node, since dropping it whole
+            // would recurse as deep as the tree.
+            let mut values = vec![super::to_sexp_json(&tree, code.as_bytes())];
+            let mut parentheses = 0;
+            while let Some(mut value) = values.pop() {
+                parentheses += usize::from(value["kind"] == "parentheses");
+                values.append(value["children"].as_array_mut().unwrap());
+            }
+            assert_eq!(parentheses, depth);
+        }
+    }
+
+    /// Checks that `code` parses the same with its line feeds replaced.
+    fn assert_line_ending_parses_identically(code: &str, ending: &str) {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&super::language()).unwrap();
+        let expected = parser.parse(code, None).unwrap();
+        assert!(!expected.root_node().has_error());
+        let code = code.replace('\n', ending);
+        let tree = parser.parse(&code, None).unwrap();
+        assert_eq!(tree.root_node().to_sexp(), expected.root_node().to_sexp());
+        let comment = tree.root_node().child(0).unwrap().named_child(3).unwrap();
+        assert_eq!(comment.utf8_text(code.as_bytes()).unwrap(), "\\* comment");
+    }
+
+    #[test]
+    fn test_crlf_line_endings() {
+        let code = "---- MODULE Test ----
+\\* comment
+op ==
+  /\\ a
+  /\\ (* text
+        text *) \\/ b
+     \\/ c
+  /\\ d
+====
+";
+        assert_line_ending_parses_identically(code, "\r\n");
+    }
+
+    #[test]
+    fn test_carriage_return_line_endings() {
+        // Jlist columns in the middle of a line come from tree-sitter, which
+        // only counts line feeds, so there are no jlists here.
+        let code = "---- MODULE Test ----
+\\* comment
+op == 1
+(* text
+   text *)
+====
+";
+        assert_line_ending_parses_identically(code, "\r");
+    }
+
+    /// Highlight `code` with the configuration for `names`, returning each
+    /// highlighted piece of text and the innermost highlight name applied.
+    #[cfg(feature = "highlight")]
+    fn highlight<'a>(names: &[&'a str], code: &'a str) -> Vec<(&'a str, &'a str)> {
+        use tree_sitter_highlight::{HighlightEvent, Highlighter};
+        let config = super::highlight_configuration(names).expect("Error loading highlight config");
+        let mut highlighter = Highlighter::new();
+        let events = highlighter
+            .highlight(&config, code.as_bytes(), None, |language| {
+                (language == "tlaplus").then_some(&config)
+            })
+            .expect("Error highlighting code");
+        let mut stack = Vec::new();
+        let mut highlights = Vec::new();
+        for event in events {
+            match event.unwrap() {
+                HighlightEvent::HighlightStart(h) => stack.push(h.0),
+                HighlightEvent::HighlightEnd => {
+                    stack.pop();
+                }
+                HighlightEvent::Source { start, end } => {
+                    if let Some(&h) = stack.last() {
+                        highlights.push((&code[start..end], names[h]));
+                    }
+                }
+            }
+        }
+        highlights
+    }
+
+    #[cfg(feature = "highlight")]
+    #[test]
+    fn test_highlight_configuration() {
+        let names = ["comment", "keyword", "module"];
+        let code = "---- MODULE Test ----\nEXTENDS Naturals\n(* --algorithm A\nbegin skip;\nend algorithm; *)\n====\n";
+        let highlights = highlight(&names, code);
+        assert!(highlights.contains(&("MODULE", "keyword")));
+        assert!(highlights.contains(&("begin", "keyword")));
+        assert!(highlights.contains(&("EXTENDS", "keyword")));
+        assert!(highlights.contains(&("Test", "module")));
+    }
+
+    #[cfg(feature = "highlight")]
+    #[test]
+    fn test_highlight_configuration_bound_variables() {
+        let names = ["keyword", "operator", "variable", "variable.parameter"];
+        let code = r#"
+---- MODULE Test ----
+Spec == \A x \in S : Op(x)
+Apply(Op(_), y) == Op(y)
+====
+"#;
+        let highlights = highlight(&names, code);
+        let captures = |text| {
+            highlights
+                .iter()
+                .filter(|&&(t, _)| t == text)
+                .map(|&(_, name)| name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(captures("x"), ["variable.parameter"; 2]);
+        assert_eq!(captures("y"), ["variable.parameter"; 2]);
+        assert_eq!(captures("S"), ["variable"]);
+        assert_eq!(
+            captures("Op"),
+            ["variable", "variable.parameter", "variable.parameter"]
+        );
+    }
 }
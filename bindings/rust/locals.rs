@@ -0,0 +1,250 @@
+//! Name resolution driven by the locals query, and lookup of references.
+
+use std::cmp::Reverse;
+use std::sync::OnceLock;
+
+use tree_sitter::{Node, Query, QueryCursor, Range, Tree};
+
+use crate::{fields, named_descendants, node_kinds, LOCALS_QUERY};
+
+fn locals_query() -> &'static Query {
+    static QUERY: OnceLock<Query> = OnceLock::new();
+    QUERY.get_or_init(|| {
+        Query::new(&crate::language(), LOCALS_QUERY).expect("Error loading locals query")
+    })
+}
+
+/// The role of a node captured by the locals query. Ordered so that a scope
+/// is opened before a definition or reference starting at the same byte.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Role {
+    Scope,
+    Definition,
+    Reference,
+}
+
+struct Scope<'tree> {
+    node: Node<'tree>,
+    definitions: Vec<Node<'tree>>,
+}
+
+/// Find the definition that the reference at `byte_offset` binds to, such
+/// as an operator parameter, a quantifier variable or a LET definition.
+///
+/// Scopes, definitions and references come from [LOCALS_QUERY]. A reference
+/// binds to the closest enclosing definition of the same name that precedes
+/// it. Returns the range of the defining identifier, or `None` if there is
+/// no reference at the offset or it is not defined within the tree.
+pub fn resolve_local(tree: &Tree, src: &[u8], byte_offset: usize) -> Option<Range> {
+    let query = locals_query();
+    let names = query.capture_names();
+    let mut captures = Vec::new();
+    let mut cursor = QueryCursor::new();
+    for (m, i) in cursor.captures(query, tree.root_node(), src) {
+        let capture = m.captures[i];
+        let role = match names[capture.index as usize] {
+            "local.scope" => Role::Scope,
+            "local.definition" => Role::Definition,
+            "local.reference" => Role::Reference,
+            _ => continue,
+        };
+        captures.push((capture.node, role));
+    }
+    captures.sort_by_key(|(node, role)| (node.start_byte(), Reverse(node.end_byte()), *role));
+    captures.dedup_by_key(|(node, role)| (node.id(), *role));
+
+    let mut scopes: Vec<Scope> = Vec::new();
+    for (node, role) in captures {
+        if node.start_byte() > byte_offset {
+            break;
+        }
+        while scopes
+            .last()
+            .is_some_and(|scope| scope.node.end_byte() <= node.start_byte())
+        {
+            scopes.pop();
+        }
+        match role {
+            Role::Scope => scopes.push(Scope {
+                node,
+                definitions: Vec::new(),
+            }),
+            Role::Definition => {
+                let outer = usize::from(names_scope(node, &scopes));
+                let index = scopes.len().checked_sub(1 + outer);
+                if let Some(scope) = index.and_then(|i| scopes.get_mut(i)) {
+                    scope.definitions.push(node);
+                }
+            }
+            Role::Reference if node.byte_range().contains(&byte_offset) => {
+                let target = name(node, src)?;
+                return scopes
+                    .iter()
+                    .rev()
+                    .flat_map(|scope| scope.definitions.iter().rev())
+                    .find(|&&def| name(def, src) == Some(target))
+                    .map(|def| def.range());
+            }
+            Role::Reference => {}
+        }
+    }
+    None
+}
+
+/// Get the range of every reference to an operator, variable or other symbol
+/// called `name`, in source order. Mentions of the name in strings and
+/// comments are not references, nor are record fields like `r.name` or the
+/// definitions of the name.
+///
+/// References are matched by name alone, so they may bind to different
+/// definitions of the same name; use [resolve_local] to tell them apart.
+pub fn references(tree: &Tree, src: &[u8], name: &str) -> Vec<Range> {
+    named_descendants(tree)
+        .filter(|node| node.kind_id() == node_kinds::IDENTIFIER_REF.id())
+        .filter(|node| !is_record_field(*node))
+        .filter(|node| node.utf8_text(src).is_ok_and(|text| text == name))
+        .map(|node| node.range())
+        .collect()
+}
+
+/// Whether `node` names a record field, as in `r.field` or `!.field`.
+fn is_record_field(node: Node) -> bool {
+    node.prev_sibling().is_some_and(|prev| prev.kind() == ".")
+}
+
+/// Get the name a definition or reference node stands for. Proof step ids
+/// can end in dots, which references to the step leave out.
+fn name<'a>(node: Node, src: &'a [u8]) -> Option<&'a str> {
+    let text = node.utf8_text(src).ok()?;
+    if node.kind_id() == node_kinds::PROOF_STEP_ID.id() {
+        Some(text.trim_end_matches('.'))
+    } else {
+        Some(text)
+    }
+}
+
+/// Whether `node` is the name of the innermost scope, like the name of an
+/// operator definition. Such a name is visible in the enclosing scope.
+fn names_scope(node: Node, scopes: &[Scope]) -> bool {
+    scopes
+        .last()
+        .is_some_and(|scope| scope.node.child_by_field_id(fields::NAME.id()) == Some(node))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Resolves the `n`th occurrence of `name` in `code` and returns the byte
+    /// offset of the definition it binds to.
+    fn resolve(code: &str, name: &str, n: usize) -> Option<usize> {
+        let (offset, _) = code.match_indices(name).nth(n).unwrap();
+        let tree = crate::parse_with(code, true).unwrap();
+        resolve_local(&tree, code.as_bytes(), offset).map(|range| range.start_byte)
+    }
+
+    #[test]
+    fn test_quantifier_variable() {
+        let code = "---- MODULE Test ----\nop(x) == \\A x \\in S : x > 0\n====\n";
+        let bound = code.find("\\A x").unwrap() + 3;
+        assert_eq!(resolve(code, "x", 2), Some(bound));
+    }
+
+    #[test]
+    fn test_let_definition() {
+        let code = "---- MODULE Test ----\nop == LET f(y) == y + 1 IN f(2) + f(3)\n====\n";
+        let def = code.find("f(y)").unwrap();
+        assert_eq!(resolve(code, "f", 1), Some(def));
+        assert_eq!(resolve(code, "f", 2), Some(def));
+        assert_eq!(resolve(code, "y", 1), Some(def + 2));
+    }
+
+    #[test]
+    fn test_operator_parameter() {
+        let code = "---- MODULE Test ----\nx == 0\nop(x) == x + 1\nother == x\n====\n";
+        let param = code.find("op(x)").unwrap() + 3;
+        assert_eq!(resolve(code, "x", 2), Some(param));
+        assert_eq!(resolve(code, "x", 3), Some(code.find("x ==").unwrap()));
+    }
+
+    #[test]
+    fn test_function_and_lambda_parameters() {
+        let code = "---- MODULE Test ----\nf[a \\in S, <<b>> \\in T] == a + b\nop == F(LAMBDA c : c)\n====\n";
+        assert_eq!(resolve(code, "a", 1), Some(code.find("a \\in").unwrap()));
+        assert_eq!(resolve(code, "b", 1), Some(code.find("b>>").unwrap()));
+        assert_eq!(resolve(code, "c", 1), Some(code.find("c :").unwrap()));
+    }
+
+    #[test]
+    fn test_higher_order_parameters() {
+        let code = "---- MODULE Test ----\nop(F(_), _ ** _) == F(1) ** F(2)\n====\n";
+        let decl = code.find("F(_)").unwrap();
+        assert_eq!(resolve(code, "F", 1), Some(decl));
+        assert_eq!(resolve(code, "F", 2), Some(decl));
+        assert_eq!(resolve(code, "**", 1), Some(code.find("**").unwrap()));
+    }
+
+    #[test]
+    fn test_let_shadowing_parameter() {
+        let code = "---- MODULE Test ----\nop(a) == a + LET a == 1 IN a\n====\n";
+        let param = code.find("op(a)").unwrap() + 3;
+        let def = code.find("LET a").unwrap() + 4;
+        assert_eq!(resolve(code, "a", 1), Some(param));
+        assert_eq!(resolve(code, "a", 3), Some(def));
+    }
+
+    #[test]
+    fn test_recursive_function() {
+        let code = "---- MODULE Test ----\nf[n \\in Nat] == IF n = 0 THEN 1 ELSE f[n - 1]\n====\n";
+        assert_eq!(resolve(code, "f", 1), Some(code.find('f').unwrap()));
+    }
+
+    #[test]
+    fn test_proof_step_reference() {
+        let code = r#"
+---- MODULE Test ----
+THEOREM TRUE
+<1>1. TRUE
+  OBVIOUS
+<1>2. TRUE
+  <2>1. TRUE
+    BY <1>1
+  <2> QED
+<1> QED BY <1>1, <1>2
+====
+"#;
+        let first = code.find("<1>1.").unwrap();
+        let second = code.find("<1>2.").unwrap();
+        assert_eq!(resolve(code, "<1>1", 1), Some(first));
+        assert_eq!(resolve(code, "<1>1", 2), Some(first));
+        assert_eq!(resolve(code, "<1>2", 1), Some(second));
+    }
+
+    #[test]
+    fn test_references() {
+        let code = r#"
+---- MODULE Test ----
+VARIABLE x
+\* x is incremented
+Next == x' = x + 1 /\ PrintT("x") /\ [r EXCEPT !.x = 1].x = x
+(* and x is a counter *)
+====
+"#;
+        let tree = crate::parse_with(code, true).unwrap();
+        let starts: Vec<_> = references(&tree, code.as_bytes(), "x")
+            .into_iter()
+            .map(|range| range.start_byte)
+            .collect();
+        let next = code.find("Next").unwrap();
+        let last = code.rfind("= x").unwrap() + 2;
+        assert_eq!(starts, [next + 8, next + 13, last]);
+        assert!(references(&tree, code.as_bytes(), "y").is_empty());
+    }
+
+    #[test]
+    fn test_unresolved() {
+        let code = "---- MODULE Test ----\nop == x + 1\n====\n";
+        assert_eq!(resolve(code, "x", 0), None);
+        assert_eq!(resolve(code, "op", 0), None);
+    }
+}
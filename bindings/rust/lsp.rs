@@ -0,0 +1,91 @@
+//! Conversion of tree positions to those of the Language Server Protocol.
+//!
+//! Tree-sitter counts columns in bytes, while LSP counts them in UTF-16 code
+//! units, so the two differ on any line with a Unicode operator like `∈`
+//! before the position.
+
+use tree_sitter::Node;
+
+/// Get the zero-based LSP `(line, character)` position of `byte` in `src`,
+/// with the character counted in UTF-16 code units. Lines end at `\n`,
+/// `\r\n` or a lone `\r`, as in LSP.
+///
+/// A byte past the end of `src` is taken as its end, and a byte within a
+/// multi-byte character as the start of that character. This scans `src`
+/// from the start, so it takes time linear in `byte`.
+pub fn byte_to_lsp_position(src: &str, byte: usize) -> (u32, u32) {
+    let mut end = byte.min(src.len());
+    while !src.is_char_boundary(end) {
+        end -= 1;
+    }
+    let bytes = src.as_bytes();
+    let mut line = 0;
+    let mut line_start = 0;
+    for (i, &b) in bytes[..end].iter().enumerate() {
+        match b {
+            b'\n' if i > 0 && bytes[i - 1] == b'\r' => line_start = i + 1,
+            b'\n' | b'\r' => {
+                line += 1;
+                line_start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let character = src[line_start..end].encode_utf16().count();
+    (line, character as u32)
+}
+
+/// Get the LSP start and end positions of `node`, as computed by
+/// [byte_to_lsp_position].
+pub fn node_lsp_range(node: &Node, src: &str) -> ((u32, u32), (u32, u32)) {
+    (
+        byte_to_lsp_position(src, node.start_byte()),
+        byte_to_lsp_position(src, node.end_byte()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_positions() {
+        let code = "ab\ncd\n";
+        assert_eq!(byte_to_lsp_position(code, 0), (0, 0));
+        assert_eq!(byte_to_lsp_position(code, 2), (0, 2));
+        assert_eq!(byte_to_lsp_position(code, 4), (1, 1));
+        assert_eq!(byte_to_lsp_position(code, 6), (2, 0));
+        assert_eq!(byte_to_lsp_position(code, 100), (2, 0));
+    }
+
+    #[test]
+    fn test_unicode_operator_column() {
+        let code = "---- MODULE Test ----\nop == x ∈ S\n====\n";
+        let tree = crate::parse_with(code, true).unwrap();
+        let module = tree.root_node().named_child(0).unwrap();
+        let definition = module.named_child(3).unwrap();
+        let rhs = definition
+            .child_by_field_name("definition")
+            .unwrap()
+            .child_by_field_name("rhs")
+            .unwrap();
+        assert_eq!(rhs.start_position().column, 12);
+        assert_eq!(node_lsp_range(&rhs, code), ((1, 10), (1, 11)));
+        let in_op = code.find('∈').unwrap();
+        assert_eq!(byte_to_lsp_position(code, in_op + 1), (1, 8));
+    }
+
+    #[test]
+    fn test_surrogate_pairs() {
+        let code = "op == \"😀\" = s";
+        assert_eq!(byte_to_lsp_position(code, code.len()), (0, 14));
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let code = "a\r\nb\rc\nd";
+        assert_eq!(byte_to_lsp_position(code, code.find('b').unwrap()), (1, 0));
+        assert_eq!(byte_to_lsp_position(code, code.find('c').unwrap()), (2, 0));
+        assert_eq!(byte_to_lsp_position(code, code.find('d').unwrap()), (3, 0));
+    }
+}
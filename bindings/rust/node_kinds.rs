@@ -0,0 +1,75 @@
+//! Ids of the named node kinds in this grammar, for comparing against
+//! [`Node::kind_id`][] instead of matching on [`Node::kind`][] strings.
+//!
+//! ```
+//! use tree_sitter_tlaplus::node_kinds;
+//!
+//! let mut parser = tree_sitter::Parser::new();
+//! parser.set_language(&tree_sitter_tlaplus::language()).unwrap();
+//! let tree = parser.parse("---- MODULE Test ----\n====\n", None).unwrap();
+//! let module = tree.root_node().named_child(0).unwrap();
+//! assert_eq!(module.kind_id(), node_kinds::MODULE.id());
+//! ```
+//!
+//! [`Node::kind_id`]: https://docs.rs/tree-sitter/*/tree_sitter/struct.Node.html#method.kind_id
+//! [`Node::kind`]: https://docs.rs/tree-sitter/*/tree_sitter/struct.Node.html#method.kind
+
+use std::sync::OnceLock;
+
+/// A named node kind whose id is looked up in the [language][] on first use.
+///
+/// [language]: crate::language
+pub struct NodeKind {
+    name: &'static str,
+    id: OnceLock<u16>,
+}
+
+impl NodeKind {
+    const fn new(name: &'static str) -> Self {
+        NodeKind {
+            name,
+            id: OnceLock::new(),
+        }
+    }
+
+    /// The name of this node kind, as returned by [`Node::kind`][].
+    ///
+    /// [`Node::kind`]: https://docs.rs/tree-sitter/*/tree_sitter/struct.Node.html#method.kind
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The id of this node kind, as returned by [`Node::kind_id`][].
+    ///
+    /// [`Node::kind_id`]: https://docs.rs/tree-sitter/*/tree_sitter/struct.Node.html#method.kind_id
+    pub fn id(&self) -> u16 {
+        *self
+            .id
+            .get_or_init(|| crate::language().id_for_node_kind(self.name, true))
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/node_kinds.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_kind_ids() {
+        for kind in [&MODULE, &OPERATOR_DEFINITION, &PROOF_STEP, &IDENTIFIER_REF] {
+            assert_ne!(kind.id(), 0, "{} has no id", kind.name());
+        }
+
+        let code = "---- MODULE Test ----\nop == x\n====\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&crate::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let module = tree.root_node().named_child(0).unwrap();
+        assert_eq!(module.kind_id(), MODULE.id());
+        let def = module.named_child(3).unwrap();
+        assert_eq!(def.kind_id(), OPERATOR_DEFINITION.id());
+        let body = def.child_by_field_name("definition").unwrap();
+        assert_eq!(body.kind_id(), IDENTIFIER_REF.id());
+    }
+}
@@ -0,0 +1,558 @@
+//! Helpers extracting the high-level structure of a parsed spec.
+
+use tree_sitter::{Node, Range, Tree};
+
+use crate::{fields, node_kinds, walk::walk};
+
+/// Get the top-level module nodes of the tree, in source order.
+fn modules(tree: &Tree) -> impl Iterator<Item = Node<'_>> {
+    let root = tree.root_node();
+    (0..root.named_child_count())
+        .filter_map(move |i| root.named_child(i))
+        .filter(|node| node.kind_id() == node_kinds::MODULE.id())
+}
+
+/// Get the name of the first module declared in the tree.
+///
+/// Returns `None` if the tree contains no module, for example when parsing
+/// an empty file or a bare snippet of TLA⁺.
+pub fn module_name<'a>(tree: &Tree, src: &'a [u8]) -> Option<&'a str> {
+    modules(tree)
+        .next()?
+        .child_by_field_id(fields::NAME.id())?
+        .utf8_text(src)
+        .ok()
+}
+
+/// Get the module whose contents the other helpers describe: the first
+/// top-level module, or the root of a snippet without an enclosing module.
+fn outer_scope(tree: &Tree) -> Node<'_> {
+    modules(tree).next().unwrap_or_else(|| tree.root_node())
+}
+
+/// Get the names of the modules extended by the first module in the tree, in
+/// source order. EXTENDS clauses of nested submodules are not included.
+pub fn extends(tree: &Tree, src: &[u8]) -> Vec<String> {
+    let scope = outer_scope(tree);
+    let mut cursor = scope.walk();
+    let Some(clause) = scope
+        .named_children(&mut cursor)
+        .find(|node| node.kind_id() == node_kinds::EXTENDS.id())
+    else {
+        return Vec::new();
+    };
+    let mut cursor = clause.walk();
+    clause
+        .named_children(&mut cursor)
+        .filter(|node| node.kind_id() == node_kinds::IDENTIFIER_REF.id())
+        .filter_map(|node| node.utf8_text(src).ok())
+        .map(String::from)
+        .collect()
+}
+
+/// The kind of symbol introduced by a top-level [Definition].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DefKind {
+    /// `op(a, b) == ...`, including `M == INSTANCE ...`
+    Operator,
+    /// `f[x \in S] == ...`
+    Function,
+    /// `CONSTANT c`
+    Constant,
+    /// `VARIABLE v`
+    Variable,
+    /// `THEOREM thm == ...`
+    Theorem,
+    /// `Next == x' = x + 1`, an operator definition found by [actions]
+    Action,
+}
+
+/// A symbol introduced at the top level of a module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Definition {
+    /// The name of the symbol.
+    pub name: String,
+    /// What kind of symbol this is.
+    pub kind: DefKind,
+    /// The range of the definition or declaration introducing the symbol.
+    pub range: Range,
+}
+
+/// Get the symbols defined or declared at the top level of the first module
+/// in the tree, in source order. Definitions nested in LET expressions,
+/// proofs, or submodules are not included.
+pub fn definitions(tree: &Tree, src: &[u8]) -> Vec<Definition> {
+    let mut definitions = Vec::new();
+    let scope = outer_scope(tree);
+    let mut cursor = scope.walk();
+    for unit in scope.named_children(&mut cursor) {
+        let Some(unit) = unwrap_local(unit) else {
+            continue;
+        };
+        let kind = unit.kind_id();
+        if kind == node_kinds::OPERATOR_DEFINITION.id()
+            || kind == node_kinds::MODULE_DEFINITION.id()
+        {
+            push_named(&mut definitions, unit, DefKind::Operator, src);
+        } else if kind == node_kinds::FUNCTION_DEFINITION.id() {
+            push_named(&mut definitions, unit, DefKind::Function, src);
+        } else if kind == node_kinds::THEOREM.id() {
+            push_named(&mut definitions, unit, DefKind::Theorem, src);
+        } else if kind == node_kinds::CONSTANT_DECLARATION.id() {
+            let mut cursor = unit.walk();
+            for decl in unit.named_children(&mut cursor) {
+                if decl.kind_id() == node_kinds::OPERATOR_DECLARATION.id() {
+                    push_named(&mut definitions, decl, DefKind::Constant, src);
+                } else if decl.kind_id() == node_kinds::IDENTIFIER.id() {
+                    push(&mut definitions, decl, decl, DefKind::Constant, src);
+                }
+            }
+        } else if kind == node_kinds::VARIABLE_DECLARATION.id() {
+            let mut cursor = unit.walk();
+            for decl in unit.named_children(&mut cursor) {
+                if decl.kind_id() == node_kinds::IDENTIFIER.id() {
+                    push(&mut definitions, decl, decl, DefKind::Variable, src);
+                }
+            }
+        }
+    }
+    definitions
+}
+
+/// Get the operator definitions at the top level of the first module in the
+/// tree which are actions, in source order: those whose body contains a
+/// primed expression or UNCHANGED. Only the body itself is examined, so
+/// `Next == A \/ B` is not found even if `A` and `B` are actions.
+///
+/// Primes within `[A]_v`, `<<A>>_v` and fairness conditions do not count, as
+/// these subformulas make a temporal formula rather than an action. Unlike
+/// [definitions], the returned definitions have the [DefKind::Action] kind.
+pub fn actions(tree: &Tree, src: &[u8]) -> Vec<Definition> {
+    let mut actions = Vec::new();
+    let scope = outer_scope(tree);
+    let mut cursor = scope.walk();
+    for unit in scope.named_children(&mut cursor) {
+        let Some(unit) = unwrap_local(unit) else {
+            continue;
+        };
+        if unit.kind_id() == node_kinds::OPERATOR_DEFINITION.id()
+            && unit
+                .child_by_field_id(fields::DEFINITION.id())
+                .is_some_and(is_action)
+        {
+            push_named(&mut actions, unit, DefKind::Action, src);
+        }
+    }
+    actions
+}
+
+/// Whether the expression contains a prime or UNCHANGED outside of any
+/// temporal step expression or fairness condition.
+fn is_action(expr: Node) -> bool {
+    let mut found = false;
+    walk(expr, |node| {
+        let kind = node.kind_id();
+        found |= kind == node_kinds::PRIME.id() || kind == node_kinds::UNCHANGED.id();
+        let is_temporal = kind == node_kinds::STEP_EXPR_OR_STUTTER.id()
+            || kind == node_kinds::STEP_EXPR_NO_STUTTER.id()
+            || kind == node_kinds::FAIRNESS.id();
+        !found && !is_temporal
+    });
+    found
+}
+
+/// Records a definition named by the `name` field of the given node, if any.
+fn push_named(definitions: &mut Vec<Definition>, node: Node, kind: DefKind, src: &[u8]) {
+    if let Some(name) = node.child_by_field_id(fields::NAME.id()) {
+        push(definitions, name, node, kind, src);
+    }
+}
+
+/// Records a definition named by the text of the given node.
+fn push(definitions: &mut Vec<Definition>, name: Node, range: Node, kind: DefKind, src: &[u8]) {
+    if let Ok(name) = name.utf8_text(src) {
+        definitions.push(Definition {
+            name: name.to_string(),
+            kind,
+            range: range.range(),
+        });
+    }
+}
+
+/// Get the definition wrapped by a LOCAL definition, skipping any comments
+/// after the keyword, or the unit itself if it is not LOCAL.
+fn unwrap_local(unit: Node) -> Option<Node> {
+    if unit.kind_id() != node_kinds::LOCAL_DEFINITION.id() {
+        return Some(unit);
+    }
+    let mut cursor = unit.walk();
+    let definition = unit
+        .named_children(&mut cursor)
+        .find(|node| !node.is_extra());
+    definition
+}
+
+/// An INSTANCE of another module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instance {
+    /// The name of the instantiated module.
+    pub module: String,
+    /// Whether the instance is marked LOCAL.
+    pub local: bool,
+    /// The `WITH` substitutions in source order, as the name of each
+    /// substituted parameter and the range of the expression replacing it.
+    pub substitutions: Vec<(String, Range)>,
+}
+
+/// Get the modules instantiated at the top level of the first module in the
+/// tree, in source order. This includes both bare `INSTANCE M` statements and
+/// named `N == INSTANCE M` definitions.
+pub fn instances(tree: &Tree, src: &[u8]) -> Vec<Instance> {
+    let scope = outer_scope(tree);
+    let mut cursor = scope.walk();
+    scope
+        .named_children(&mut cursor)
+        .filter_map(|unit| {
+            let local = unit.kind_id() == node_kinds::LOCAL_DEFINITION.id();
+            let unit = unwrap_local(unit)?;
+            let instance = if unit.kind_id() == node_kinds::MODULE_DEFINITION.id() {
+                unit.child_by_field_id(fields::DEFINITION.id())?
+            } else {
+                unit
+            };
+            if instance.kind_id() != node_kinds::INSTANCE.id() {
+                return None;
+            }
+            instance_of(instance, local, src)
+        })
+        .collect()
+}
+
+fn instance_of(instance: Node, local: bool, src: &[u8]) -> Option<Instance> {
+    let mut cursor = instance.walk();
+    let module = instance
+        .named_children(&mut cursor)
+        .find(|node| node.kind_id() == node_kinds::IDENTIFIER_REF.id())?
+        .utf8_text(src)
+        .ok()?
+        .to_string();
+    let substitutions = instance
+        .named_children(&mut cursor)
+        .filter(|node| node.kind_id() == node_kinds::SUBSTITUTION.id())
+        .filter_map(|substitution| {
+            let name = substitution
+                .child_by_field_id(fields::TARGET.id())?
+                .utf8_text(src)
+                .ok()?;
+            let value = substitution.child_by_field_id(fields::EXPRESSION.id())?;
+            Some((name.to_string(), value.range()))
+        })
+        .collect();
+    Some(Instance {
+        module,
+        local,
+        substitutions,
+    })
+}
+
+/// A constant or variable declared by a module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decl {
+    /// The name of the declared symbol.
+    pub name: String,
+    /// The number of arguments taken by a constant operator like `f(_, _)`,
+    /// or 0 for plain constants and variables.
+    pub arity: usize,
+    /// The range of the declaration of this symbol within its clause.
+    pub range: Range,
+}
+
+/// The constants and variables declared by a module.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Declarations {
+    /// Symbols declared in CONSTANT clauses, in source order.
+    pub constants: Vec<Decl>,
+    /// Symbols declared in VARIABLE clauses, in source order.
+    pub variables: Vec<Decl>,
+}
+
+/// Get the constants and variables declared at the top level of the first
+/// module in the tree.
+pub fn declarations(tree: &Tree, src: &[u8]) -> Declarations {
+    let mut declarations = Declarations::default();
+    let scope = outer_scope(tree);
+    let mut cursor = scope.walk();
+    for unit in scope.named_children(&mut cursor) {
+        let decls = if unit.kind_id() == node_kinds::CONSTANT_DECLARATION.id() {
+            &mut declarations.constants
+        } else if unit.kind_id() == node_kinds::VARIABLE_DECLARATION.id() {
+            &mut declarations.variables
+        } else {
+            continue;
+        };
+        let mut cursor = unit.walk();
+        decls.extend(
+            unit.named_children(&mut cursor)
+                .filter_map(|decl| decl_of(decl, src)),
+        );
+    }
+    declarations
+}
+
+fn decl_of(decl: Node, src: &[u8]) -> Option<Decl> {
+    let (name, arity) = if decl.kind_id() == node_kinds::OPERATOR_DECLARATION.id() {
+        let mut cursor = decl.walk();
+        let arity = decl
+            .named_children(&mut cursor)
+            .filter(|node| node.kind_id() == node_kinds::PLACEHOLDER.id())
+            .count();
+        (decl.child_by_field_id(fields::NAME.id())?, arity)
+    } else if decl.kind_id() == node_kinds::IDENTIFIER.id() {
+        (decl, 0)
+    } else {
+        return None;
+    };
+    Some(Decl {
+        name: name.utf8_text(src).ok()?.to_string(),
+        arity,
+        range: decl.range(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_name() {
+        let code = "---- MODULE First ----\n====\n---- MODULE Second ----\n====\n";
+        assert_eq!(
+            module_name(&crate::parse_with(code, true).unwrap(), code.as_bytes()),
+            Some("First")
+        );
+    }
+
+    #[test]
+    fn test_module_name_after_byte_order_mark() {
+        let code = "\u{feff}---- MODULE Test ----\nop == 1\n====\n";
+        let tree = crate::parse_with(code, true).unwrap();
+        assert!(!tree.root_node().has_error());
+        assert_eq!(module_name(&tree, code.as_bytes()), Some("Test"));
+    }
+
+    #[test]
+    fn test_module_name_empty_file() {
+        assert_eq!(
+            module_name(&crate::parse_with("", true).unwrap(), b""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extends() {
+        let code = "---- MODULE Test ----\nEXTENDS Naturals, Sequences,\n  FiniteSets\n====\n";
+        assert_eq!(
+            extends(&crate::parse_with(code, true).unwrap(), code.as_bytes()),
+            ["Naturals", "Sequences", "FiniteSets"]
+        );
+    }
+
+    #[test]
+    fn test_extends_skips_comments() {
+        let code = r#"
+---- MODULE Test ----
+EXTENDS Naturals, \* numbers
+  (* sequences *) Sequences
+====
+"#;
+        assert_eq!(
+            extends(&crate::parse_with(code, true).unwrap(), code.as_bytes()),
+            ["Naturals", "Sequences"]
+        );
+    }
+
+    #[test]
+    fn test_extends_none() {
+        let code = "---- MODULE Test ----\nop == 1\n====\n";
+        assert!(extends(&crate::parse_with(code, true).unwrap(), code.as_bytes()).is_empty());
+    }
+
+    #[test]
+    fn test_extends_ignores_submodules() {
+        let code = r#"
+---- MODULE Outer ----
+EXTENDS Naturals
+---- MODULE Inner ----
+EXTENDS Sequences
+====
+op == 1
+====
+"#;
+        assert_eq!(
+            extends(&crate::parse_with(code, true).unwrap(), code.as_bytes()),
+            ["Naturals"]
+        );
+        let code =
+            "---- MODULE Outer ----\n---- MODULE Inner ----\nEXTENDS Sequences\n====\n====\n";
+        assert!(extends(&crate::parse_with(code, true).unwrap(), code.as_bytes()).is_empty());
+    }
+
+    #[test]
+    fn test_definitions() {
+        let code = r#"
+---- MODULE Test ----
+CONSTANTS N, F(_, _)
+VARIABLES x, y
+RECURSIVE fact(_)
+fact(n) == IF n = 0 THEN 1 ELSE n * fact(n - 1)
+sq[n \in Nat] == n * n
+LOCAL \* the initial state
+  init == LET local == 1 IN x = local
+THEOREM thm == init => TRUE
+THEOREM TRUE
+---- MODULE Inner ----
+inner == 1
+====
+====
+"#;
+        let tree = crate::parse_with(code, true).unwrap();
+        let defs: Vec<_> = definitions(&tree, code.as_bytes())
+            .into_iter()
+            .map(|def| (def.name, def.kind, def.range.start_point.row))
+            .collect();
+        assert_eq!(
+            defs,
+            [
+                ("N".to_string(), DefKind::Constant, 2),
+                ("F".to_string(), DefKind::Constant, 2),
+                ("x".to_string(), DefKind::Variable, 3),
+                ("y".to_string(), DefKind::Variable, 3),
+                ("fact".to_string(), DefKind::Operator, 5),
+                ("sq".to_string(), DefKind::Function, 6),
+                ("init".to_string(), DefKind::Operator, 8),
+                ("thm".to_string(), DefKind::Theorem, 9),
+            ]
+        );
+        let fact = &definitions(&tree, code.as_bytes())[4];
+        assert_eq!(
+            &code[fact.range.start_byte..fact.range.end_byte],
+            "fact(n) == IF n = 0 THEN 1 ELSE n * fact(n - 1)"
+        );
+    }
+
+    #[test]
+    fn test_definitions_skip_comments_in_declarations() {
+        let code = "---- MODULE Test ----\nCONSTANTS N, \\* the count\n  M\nVARIABLES x, (* c *) y\n====\n";
+        let defs: Vec<_> = definitions(&crate::parse_with(code, true).unwrap(), code.as_bytes())
+            .into_iter()
+            .map(|def| (def.name, def.kind))
+            .collect();
+        assert_eq!(
+            defs,
+            [
+                ("N".to_string(), DefKind::Constant),
+                ("M".to_string(), DefKind::Constant),
+                ("x".to_string(), DefKind::Variable),
+                ("y".to_string(), DefKind::Variable),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_actions() {
+        let code = r#"
+---- MODULE Test ----
+VARIABLES x, y
+TypeOK == x \in Nat /\ y \in Nat
+Init == x = 0 /\ y = 0
+LOCAL (* helper *) Inc == x' = x + 1 /\ UNCHANGED y
+LOCAL Reset == /\ y' = 0
+               /\ UNCHANGED <<x>>
+Stutter == UNCHANGED <<x, y>>
+Next == Inc \/ Reset
+Spec == Init /\ [][x' = x + 1]_x /\ WF_x(Inc)
+f[n \in Nat] == n'
+====
+"#;
+        let names: Vec<_> = actions(&crate::parse_with(code, true).unwrap(), code.as_bytes())
+            .into_iter()
+            .map(|def| {
+                assert_eq!(def.kind, DefKind::Action);
+                def.name
+            })
+            .collect();
+        assert_eq!(names, ["Inc", "Reset", "Stutter"]);
+    }
+
+    #[test]
+    fn test_instances() {
+        let code = r#"
+---- MODULE Test ----
+INSTANCE (* c *) Naturals
+LOCAL (* c *) INSTANCE Sequences
+Spec == INSTANCE \* c
+  Abstract WITH x <- y + 1, + <- -
+====
+"#;
+        let instances = instances(&crate::parse_with(code, true).unwrap(), code.as_bytes());
+        let modules: Vec<_> = instances
+            .iter()
+            .map(|instance| (instance.module.as_str(), instance.local))
+            .collect();
+        assert_eq!(
+            modules,
+            [
+                ("Naturals", false),
+                ("Sequences", true),
+                ("Abstract", false)
+            ]
+        );
+        assert!(instances[0].substitutions.is_empty());
+        assert!(instances[1].substitutions.is_empty());
+        let substitutions: Vec<_> = instances[2]
+            .substitutions
+            .iter()
+            .map(|(name, range)| (name.as_str(), &code[range.start_byte..range.end_byte]))
+            .collect();
+        assert_eq!(substitutions, [("x", "y + 1"), ("+", "-")]);
+    }
+
+    #[test]
+    fn test_declarations() {
+        let code = r#"
+---- MODULE Test ----
+CONSTANTS N, f(_, _), \* an operator
+  _ ** _
+VARIABLE x
+VARIABLES y, (* c *)
+  z
+====
+"#;
+        let Declarations {
+            constants,
+            variables,
+        } = declarations(&crate::parse_with(code, true).unwrap(), code.as_bytes());
+        let summarize = |decls: Vec<Decl>| -> Vec<_> {
+            decls
+                .into_iter()
+                .map(|decl| (decl.name, decl.arity, decl.range.start_point.row))
+                .collect()
+        };
+        assert_eq!(
+            summarize(constants),
+            [
+                ("N".to_string(), 0, 2),
+                ("f".to_string(), 2, 2),
+                ("**".to_string(), 2, 3),
+            ]
+        );
+        assert_eq!(
+            summarize(variables),
+            [
+                ("x".to_string(), 0, 4),
+                ("y".to_string(), 0, 5),
+                ("z".to_string(), 0, 6),
+            ]
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! One-call parsing of TLA⁺ source text.
+
+use std::fmt;
+
+use tree_sitter::{InputEdit, Parser, Point, Range, Tree};
+
+use crate::{diagnostics, fields, node_kinds};
+
+/// The reason [parse] rejected its input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// A short human-readable description of the first problem.
+    pub message: String,
+    /// The range of the first syntax error, or `None` if the parser produced
+    /// no tree at all.
+    pub range: Option<Range>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.range {
+            Some(range) => write!(
+                f,
+                "{}:{}: {}",
+                range.start_point.row + 1,
+                range.start_point.column + 1,
+                self.message
+            ),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `src` with a new parser for this grammar, failing on the first
+/// syntax error.
+pub fn parse(src: &str) -> Result<Tree, ParseError> {
+    parse_with(src, false)
+}
+
+/// Parse `src` with a new parser for this grammar. If `allow_errors` is set,
+/// a tree containing syntax errors is returned as is; use [diagnostics] to
+/// list them.
+pub fn parse_with(src: &str, allow_errors: bool) -> Result<Tree, ParseError> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&crate::language())
+        .expect("Error loading Tlaplus grammar");
+    let tree = parser.parse(src, None).ok_or_else(|| ParseError {
+        message: "parsing did not complete".to_string(),
+        range: None,
+    })?;
+    if allow_errors || !tree.root_node().has_error() {
+        return Ok(tree);
+    }
+    Err(first_error(&tree, src))
+}
+
+/// The text placed before an expression to parse it as an assumption.
+const EXPRESSION_PREFIX: &str = "ASSUME\n";
+
+/// Parse `src` as a single TLA⁺ expression outside of any module, like
+/// `a + b`, failing on the first syntax error or if `src` is not exactly one
+/// expression.
+///
+/// The expression is parsed as the statement of an `ASSUME` placed on the
+/// line before it, which is then edited out of the tree, so that node ranges
+/// are positions in `src`. The expression is the `statement` field of the
+/// single named child of the root:
+///
+/// ```
+/// let tree = tree_sitter_tlaplus::parse_expression("a + b").unwrap();
+/// let assumption = tree.root_node().named_child(0).unwrap();
+/// let expr = assumption.child_by_field_name("statement").unwrap();
+/// assert_eq!(expr.kind(), "bound_infix_op");
+/// assert_eq!(expr.byte_range(), 0..5);
+/// ```
+pub fn parse_expression(src: &str) -> Result<Tree, ParseError> {
+    let mut tree = parse_with(&format!("{EXPRESSION_PREFIX}{src}"), true)?;
+    tree.edit(&InputEdit {
+        start_byte: 0,
+        old_end_byte: EXPRESSION_PREFIX.len(),
+        new_end_byte: 0,
+        start_position: Point::new(0, 0),
+        old_end_position: Point::new(1, 0),
+        new_end_position: Point::new(0, 0),
+    });
+    if tree.root_node().has_error() {
+        return Err(first_error(&tree, src));
+    }
+    check_single_expression(&tree)?;
+    Ok(tree)
+}
+
+/// Check that the tree parsed by [parse_expression] holds nothing but the
+/// unnamed assumption of the expression.
+fn check_single_expression(tree: &Tree) -> Result<(), ParseError> {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let mut units = root.named_children(&mut cursor);
+    let assumption = units.next().filter(|unit| {
+        unit.kind_id() == node_kinds::ASSUMPTION.id()
+            && unit.child_by_field_id(fields::NAME.id()).is_none()
+    });
+    match (assumption, units.next()) {
+        (Some(_), None) => Ok(()),
+        (_, extra) => Err(ParseError {
+            message: "not a single expression".to_string(),
+            range: Some(extra.unwrap_or(root).range()),
+        }),
+    }
+}
+
+/// Describe the first syntax error in a tree with errors.
+fn first_error(tree: &Tree, src: &str) -> ParseError {
+    match diagnostics(tree, src.as_bytes()).into_iter().next() {
+        Some(diagnostic) => ParseError {
+            message: diagnostic.message,
+            range: Some(diagnostic.range),
+        },
+        None => ParseError {
+            message: "syntax error".to_string(),
+            range: Some(tree.root_node().range()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_module() {
+        let tree = parse("---- MODULE Test ----\nop == 1\n====\n").unwrap();
+        assert_eq!(tree.root_node().kind(), "source_file");
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let code = "---- MODULE Test ----\nop == 1\n";
+        let error = parse(code).unwrap_err();
+        assert_eq!(error.message, "unterminated module, missing ====");
+        assert_eq!(error.range.unwrap().start_point.row, 1);
+        assert_eq!(error.to_string(), "2:8: unterminated module, missing ====");
+        let tree = parse_with(code, true).unwrap();
+        assert!(tree.root_node().has_error());
+    }
+
+    #[test]
+    fn test_omitted_proof_is_not_an_error() {
+        assert!(parse("---- MODULE Test ----\nTHEOREM TRUE OMITTED\n====\n").is_ok());
+        let code = "---- MODULE Test ----\nTHEOREM TRUE OMITTED\nop == 1 +\nop2 == 2\n====\n";
+        let error = parse(code).unwrap_err();
+        assert_eq!(error.range.unwrap().start_point.row, 3);
+    }
+
+    /// Parse `src` as an expression and get the expression node's kind and
+    /// its text in `src`.
+    fn expression(src: &str) -> (String, String) {
+        let tree = parse_expression(src).unwrap();
+        let assumption = tree.root_node().named_child(0).unwrap();
+        let expr = assumption.child_by_field_name("statement").unwrap();
+        let text = expr.utf8_text(src.as_bytes()).unwrap();
+        (expr.kind().to_string(), text.to_string())
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        let (kind, text) = expression("a + b");
+        assert_eq!((kind.as_str(), text.as_str()), ("bound_infix_op", "a + b"));
+        let src = "\\A x \\in S : P(x)";
+        let (kind, text) = expression(src);
+        assert_eq!(
+            (kind.as_str(), text.as_str()),
+            ("bounded_quantification", src)
+        );
+    }
+
+    #[test]
+    fn test_parse_expression_positions() {
+        let src = "/\\ x = 1\n/\\ y \\in S\n";
+        let tree = parse_expression(src).unwrap();
+        let offset = src.find('S').unwrap();
+        let s = tree
+            .root_node()
+            .descendant_for_byte_range(offset, offset + 1)
+            .unwrap();
+        assert_eq!(s.utf8_text(src.as_bytes()).unwrap(), "S");
+        assert_eq!(s.start_position(), Point::new(1, 9));
+        let (kind, text) = expression(src);
+        assert_eq!((kind.as_str(), text.as_str()), ("conj_list", src));
+    }
+
+    #[test]
+    fn test_parse_expression_errors() {
+        let error = parse_expression("x \\in ").unwrap_err();
+        assert_eq!(error.range.unwrap().start_point.row, 0);
+        let error = parse_expression("a + b\nop == 1").unwrap_err();
+        assert_eq!(error.message, "not a single expression");
+        assert_eq!(error.to_string(), "2:1: not a single expression");
+        assert!(parse_expression("A == 1").is_err());
+        assert!(parse_expression("").is_err());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let tree = parse("").unwrap();
+        assert_eq!(tree.root_node().child_count(), 0);
+    }
+}
@@ -0,0 +1,329 @@
+//! Helpers extracting the structure of TLAPS proofs.
+
+use tree_sitter::{Node, Range, Tree};
+
+use crate::{fields, named_children, node_kinds, walk::walk};
+
+/// The kind of a [ProofStep].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepKind {
+    /// `<1>a. P`, an assertion to be proved
+    Assertion,
+    /// `<1>a. SUFFICES P`
+    Suffices,
+    /// `<1>a. CASE P`
+    Case,
+    /// `<1>a. PICK x \in S : P`
+    Pick,
+    /// `<1>a. HAVE P`
+    Have,
+    /// `<1>a. TAKE x \in S`
+    Take,
+    /// `<1>a. WITNESS e`
+    Witness,
+    /// `<1>a. DEFINE op == ...`
+    Define,
+    /// `<1>a. USE P` or `<1>a. HIDE P`
+    UseOrHide,
+    /// `<1>a. INSTANCE M`
+    Instance,
+    /// `<1>a. QED`
+    Qed,
+}
+
+/// A single step of a structured proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofStep {
+    /// The nesting depth of the step, starting at 1 for the steps of a
+    /// top-level proof. This is the actual depth even when the step is
+    /// written with `<+>` or `<*>`.
+    pub level: u32,
+    /// The step identifier as it can be referred to, like `<1>a`, or `None`
+    /// if the step is unnamed.
+    pub name: Option<String>,
+    /// What kind of step this is.
+    pub kind: StepKind,
+    /// The range of the whole step, including any nested proof.
+    pub range: Range,
+}
+
+/// Get every proof step in the tree as a flat list in source order. Steps
+/// of nested proofs directly follow the step they prove, with a greater
+/// [level](ProofStep::level).
+pub fn proof_steps(tree: &Tree, src: &[u8]) -> Vec<ProofStep> {
+    let mut steps = Vec::new();
+    // The proofs enclosing the current node, innermost last. A proof is
+    // left once the walk reaches a node starting at or after its end.
+    let mut proofs = Vec::new();
+    walk(tree.root_node(), |node| {
+        while proofs
+            .last()
+            .is_some_and(|proof: &Node| node.start_byte() >= proof.end_byte())
+        {
+            proofs.pop();
+        }
+        if is_proof(node) {
+            proofs.push(node);
+        }
+        if let Some(step) = proof_step(node, proofs.len() as u32, src) {
+            steps.push(step);
+        }
+        true
+    });
+    steps
+}
+
+fn is_proof(node: Node) -> bool {
+    node.kind_id() == node_kinds::NON_TERMINAL_PROOF.id()
+}
+
+fn proof_step(node: Node, level: u32, src: &[u8]) -> Option<ProofStep> {
+    let is_qed = node.kind_id() == node_kinds::QED_STEP.id();
+    if !is_qed && node.kind_id() != node_kinds::PROOF_STEP.id() {
+        return None;
+    }
+    // Comments may separate the step id from the body.
+    let mut parts = named_children(node).filter(|child| !child.is_extra());
+    let id = parts.next()?;
+    let kind = if is_qed {
+        StepKind::Qed
+    } else {
+        step_kind(parts.next()?)?
+    };
+    Some(ProofStep {
+        level,
+        name: step_name(id, src),
+        kind,
+        range: node.range(),
+    })
+}
+
+fn step_kind(body: Node) -> Option<StepKind> {
+    let kind = body.kind_id();
+    let step_kind = if kind == node_kinds::SUFFICES_PROOF_STEP.id() {
+        match body.child(0) {
+            Some(keyword) if keyword.kind() == "SUFFICES" => StepKind::Suffices,
+            _ => StepKind::Assertion,
+        }
+    } else if kind == node_kinds::CASE_PROOF_STEP.id() {
+        StepKind::Case
+    } else if kind == node_kinds::PICK_PROOF_STEP.id() {
+        StepKind::Pick
+    } else if kind == node_kinds::HAVE_PROOF_STEP.id() {
+        StepKind::Have
+    } else if kind == node_kinds::TAKE_PROOF_STEP.id() {
+        StepKind::Take
+    } else if kind == node_kinds::WITNESS_PROOF_STEP.id() {
+        StepKind::Witness
+    } else if kind == node_kinds::DEFINITION_PROOF_STEP.id() {
+        StepKind::Define
+    } else if kind == node_kinds::USE_OR_HIDE.id() {
+        StepKind::UseOrHide
+    } else if kind == node_kinds::INSTANCE.id() {
+        StepKind::Instance
+    } else {
+        return None;
+    };
+    Some(step_kind)
+}
+
+/// Gets the referable name of a step from its `proof_step_id`, so
+/// `<1>a.` becomes `<1>a`.
+fn step_name(id: Node, src: &[u8]) -> Option<String> {
+    let name = id.named_child(1)?;
+    if name.byte_range().is_empty() {
+        return None;
+    }
+    let level = id.named_child(0)?;
+    let text = std::str::from_utf8(&src[level.start_byte()..name.end_byte()]).ok()?;
+    Some(format!("<{text}"))
+}
+
+/// The keyword introducing a [NamedUnit].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnitKind {
+    /// `THEOREM`
+    Theorem,
+    /// `LEMMA`
+    Lemma,
+    /// `COROLLARY`
+    Corollary,
+    /// `PROPOSITION`
+    Proposition,
+    /// `ASSUME` or `ASSUMPTION`
+    Assumption,
+    /// `AXIOM`
+    Axiom,
+}
+
+/// A theorem or assumption unit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NamedUnit {
+    /// The keyword introducing the unit.
+    pub kind: UnitKind,
+    /// The name given to the unit, or `None` if it is anonymous.
+    pub name: Option<String>,
+    /// Whether a proof is attached to the unit. Always false for assumptions.
+    pub has_proof: bool,
+    /// The range of the whole unit, including any proof.
+    pub range: Range,
+}
+
+/// Get every theorem and assumption in the tree, in source order, including
+/// those of nested submodules.
+pub fn theorems(tree: &Tree, src: &[u8]) -> Vec<NamedUnit> {
+    let mut units = Vec::new();
+    walk(tree.root_node(), |node| {
+        let is_unit = node.kind_id() == node_kinds::THEOREM.id()
+            || node.kind_id() == node_kinds::ASSUMPTION.id();
+        if is_unit {
+            if let Some(unit) = named_unit(node, src) {
+                units.push(unit);
+            }
+        }
+        !is_unit
+    });
+    units
+}
+
+fn named_unit(node: Node, src: &[u8]) -> Option<NamedUnit> {
+    let kind = match node.child_by_field_id(fields::KEYWORD.id())?.kind() {
+        "THEOREM" => UnitKind::Theorem,
+        "LEMMA" => UnitKind::Lemma,
+        "COROLLARY" => UnitKind::Corollary,
+        "PROPOSITION" => UnitKind::Proposition,
+        "ASSUME" | "ASSUMPTION" => UnitKind::Assumption,
+        "AXIOM" => UnitKind::Axiom,
+        _ => return None,
+    };
+    let name = node
+        .child_by_field_id(fields::NAME.id())
+        .and_then(|name| name.utf8_text(src).ok())
+        .map(String::from);
+    Some(NamedUnit {
+        kind,
+        name,
+        has_proof: node.child_by_field_id(fields::PROOF.id()).is_some(),
+        range: node.range(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proof_steps() {
+        let code = r#"
+---- MODULE Test ----
+THEOREM TRUE
+<1>1. SUFFICES TRUE
+  OBVIOUS
+<1>2. TRUE
+  <2>a. CASE TRUE
+  <2> QED
+    BY <2>a
+<1>. QED
+  BY <1>1, <1>2
+====
+"#;
+        let steps: Vec<_> = proof_steps(&crate::parse_with(code, true).unwrap(), code.as_bytes())
+            .into_iter()
+            .map(|step| (step.level, step.name, step.kind, step.range.start_point.row))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                (1, Some("<1>1".to_string()), StepKind::Suffices, 3),
+                (1, Some("<1>2".to_string()), StepKind::Assertion, 5),
+                (2, Some("<2>a".to_string()), StepKind::Case, 6),
+                (2, None, StepKind::Qed, 7),
+                (1, None, StepKind::Qed, 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_proof_steps_with_comments() {
+        let code = r#"
+---- MODULE Test ----
+THEOREM TRUE
+<1>1. (* why *) TRUE
+<1>2. \* note
+  SUFFICES TRUE
+<1> QED
+====
+"#;
+        let steps: Vec<_> = proof_steps(&crate::parse_with(code, true).unwrap(), code.as_bytes())
+            .into_iter()
+            .map(|step| (step.name, step.kind))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                (Some("<1>1".to_string()), StepKind::Assertion),
+                (Some("<1>2".to_string()), StepKind::Suffices),
+                (None, StepKind::Qed),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_proof_steps_relative_levels() {
+        let code = r#"
+---- MODULE Test ----
+THEOREM TRUE
+<*> TRUE
+  <+> QED
+<*> QED
+====
+"#;
+        let levels: Vec<_> = proof_steps(&crate::parse_with(code, true).unwrap(), code.as_bytes())
+            .into_iter()
+            .map(|step| (step.level, step.kind))
+            .collect();
+        assert_eq!(
+            levels,
+            [
+                (1, StepKind::Assertion),
+                (2, StepKind::Qed),
+                (1, StepKind::Qed)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_theorems() {
+        let code = r#"
+---- MODULE Test ----
+ASSUME TRUE
+AXIOM ax == TRUE
+LEMMA lem == TRUE
+  OBVIOUS
+---- MODULE Inner ----
+COROLLARY TRUE
+====
+====
+"#;
+        let units: Vec<_> = theorems(&crate::parse_with(code, true).unwrap(), code.as_bytes())
+            .into_iter()
+            .map(|unit| {
+                (
+                    unit.kind,
+                    unit.name,
+                    unit.has_proof,
+                    unit.range.start_point.row,
+                )
+            })
+            .collect();
+        assert_eq!(
+            units,
+            [
+                (UnitKind::Assumption, None, false, 2),
+                (UnitKind::Axiom, Some("ax".to_string()), false, 3),
+                (UnitKind::Lemma, Some("lem".to_string()), true, 4),
+                (UnitKind::Corollary, None, false, 7),
+            ]
+        );
+    }
+}
@@ -0,0 +1,659 @@
+//! Conversion between the ASCII and Unicode spellings of TLA⁺ symbols.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use tree_sitter::{Node, Tree};
+
+use crate::{node_kinds, walk::walk};
+
+/// A symbol with both ASCII and Unicode spellings. The first spelling in
+/// each list is the one produced when converting to that form.
+struct Symbol {
+    kind: &'static str,
+    ascii: &'static [&'static str],
+    unicode: &'static [&'static str],
+}
+
+const fn symbol(
+    kind: &'static str,
+    ascii: &'static [&'static str],
+    unicode: &'static [&'static str],
+) -> Symbol {
+    Symbol {
+        kind,
+        ascii,
+        unicode,
+    }
+}
+
+#[rustfmt::skip]
+static SYMBOLS: &[Symbol] = &[
+    // Syntactic elements
+    symbol("def_eq",             &["=="],                         &["≜"]),
+    symbol("set_in",             &["\\in"],                       &["∈"]),
+    symbol("gets",               &["<-"],                         &["←", "⟵"]),
+    symbol("forall",             &["\\A", "\\forall"],            &["∀"]),
+    symbol("exists",             &["\\E", "\\exists"],            &["∃"]),
+    symbol("all_map_to",         &["|->"],                        &["↦", "⟼"]),
+    symbol("maps_to",            &["->"],                         &["→", "⟶"]),
+    symbol("langle_bracket",     &["<<"],                         &["⟨", "〈"]),
+    symbol("rangle_bracket",     &[">>"],                         &["⟩", "〉"]),
+    symbol("rangle_bracket_sub", &[">>_"],                        &["⟩_", "〉_"]),
+    symbol("case_box",           &["[]"],                         &["□"]),
+    symbol("case_arrow",         &["->"],                         &["→", "⟶"]),
+    symbol("label_as",           &["::"],                         &["∷"]),
+    symbol("bullet_conj",        &["/\\"],                        &["∧"]),
+    symbol("bullet_disj",        &["\\/"],                        &["∨"]),
+    // Number sets
+    symbol("nat_number_set",     &["Nat"],                        &["ℕ"]),
+    symbol("int_number_set",     &["Int"],                        &["ℤ"]),
+    symbol("real_number_set",    &["Real"],                       &["ℝ"]),
+    // Prefix operators
+    symbol("lnot",               &["~", "\\lnot", "\\neg"],       &["¬"]),
+    symbol("always",             &["[]"],                         &["□"]),
+    symbol("eventually",         &["<>"],                         &["◇", "⋄"]),
+    // Infix operators
+    symbol("implies",            &["=>"],                         &["⇒", "⟹"]),
+    symbol("plus_arrow",         &["-+->"],                       &["⇸", "⥅"]),
+    symbol("equiv",              &["\\equiv"],                    &["≡"]),
+    symbol("iff",                &["<=>"],                        &["⇔", "⟺"]),
+    symbol("leads_to",           &["~>"],                         &["↝", "⇝", "⤳"]),
+    symbol("land",               &["/\\", "\\land"],              &["∧"]),
+    symbol("lor",                &["\\/", "\\lor"],               &["∨"]),
+    symbol("assign",             &[":="],                         &["≔"]),
+    symbol("bnf_rule",           &["::="],                        &["⩴"]),
+    symbol("neq",                &["/=", "#"],                    &["≠"]),
+    symbol("leq",                &["<=", "=<", "\\leq"],          &["≤"]),
+    symbol("geq",                &[">=", "\\geq"],                &["≥"]),
+    symbol("approx",             &["\\approx"],                   &["≈"]),
+    symbol("rs_ttile",           &["|-"],                         &["⊢"]),
+    symbol("rd_ttile",           &["|="],                         &["⊨"]),
+    symbol("ls_ttile",           &["-|"],                         &["⊣"]),
+    symbol("ld_ttile",           &["=|"],                         &["⫤"]),
+    symbol("asymp",              &["\\asymp"],                    &["≍"]),
+    symbol("cong",               &["\\cong"],                     &["≅"]),
+    symbol("doteq",              &["\\doteq"],                    &["≐"]),
+    symbol("gg",                 &["\\gg"],                       &["≫"]),
+    symbol("ll",                 &["\\ll"],                       &["≪"]),
+    symbol("in",                 &["\\in"],                       &["∈"]),
+    symbol("notin",              &["\\notin"],                    &["∉"]),
+    symbol("prec",               &["\\prec"],                     &["≺"]),
+    symbol("succ",               &["\\succ"],                     &["≻"]),
+    symbol("preceq",             &["\\preceq"],                   &["⪯"]),
+    symbol("succeq",             &["\\succeq"],                   &["⪰"]),
+    symbol("propto",             &["\\propto"],                   &["∝"]),
+    symbol("sim",                &["\\sim"],                      &["∼"]),
+    symbol("simeq",              &["\\simeq"],                    &["≃"]),
+    symbol("sqsubset",           &["\\sqsubset"],                 &["⊏"]),
+    symbol("sqsupset",           &["\\sqsupset"],                 &["⊐"]),
+    symbol("sqsubseteq",         &["\\sqsubseteq"],               &["⊑"]),
+    symbol("sqsupseteq",         &["\\sqsupseteq"],               &["⊒"]),
+    symbol("subset",             &["\\subset"],                   &["⊂"]),
+    symbol("supset",             &["\\supset"],                   &["⊃"]),
+    symbol("subseteq",           &["\\subseteq"],                 &["⊆"]),
+    symbol("supseteq",           &["\\supseteq"],                 &["⊇"]),
+    symbol("cap",                &["\\cap", "\\intersect"],       &["∩"]),
+    symbol("cup",                &["\\cup", "\\union"],           &["∪"]),
+    symbol("oplus",              &["\\oplus", "(+)"],             &["⊕"]),
+    symbol("ominus",             &["\\ominus", "(-)"],            &["⊖"]),
+    symbol("vertvert",           &["||"],                         &["‖"]),
+    symbol("odot",               &["\\odot", "(.)"],              &["⊙"]),
+    symbol("oslash",             &["\\oslash", "(/)"],            &["⊘"]),
+    symbol("otimes",             &["\\otimes", "(\\X)"],          &["⊗"]),
+    symbol("bigcirc",            &["\\bigcirc"],                  &["◯"]),
+    symbol("bullet",             &["\\bullet"],                   &["●"]),
+    symbol("div",                &["\\div"],                      &["÷"]),
+    symbol("circ",               &["\\o", "\\circ"],              &["∘"]),
+    symbol("star",               &["\\star"],                     &["⋆"]),
+    symbol("excl",               &["!!"],                         &["‼"]),
+    symbol("qq",                 &["??"],                         &["⁇"]),
+    symbol("sqcap",              &["\\sqcap"],                    &["⊓"]),
+    symbol("sqcup",              &["\\sqcup"],                    &["⊔"]),
+    symbol("uplus",              &["\\uplus"],                    &["⊎"]),
+    symbol("times",              &["\\X", "\\times"],             &["×"]),
+    symbol("wr",                 &["\\wr"],                       &["≀"]),
+    symbol("cdot",               &["\\cdot"],                     &["⋅"]),
+    // Postfix operators
+    symbol("sup_plus",           &["^+"],                         &["⁺"]),
+];
+
+/// Rewrite every ASCII operator and keyword symbol in the given TLA⁺ source
+/// to its Unicode equivalent, leaving identifiers, strings, and comments
+/// untouched. Conjunction & disjunction lists are re-aligned as needed.
+pub fn to_unicode(src: &str) -> String {
+    convert(src, |symbol| (symbol.ascii, symbol.unicode[0]))
+}
+
+/// Rewrite every Unicode operator and keyword symbol in the given TLA⁺
+/// source to its ASCII equivalent, leaving identifiers, strings, and comments
+/// untouched. Conjunction & disjunction lists are re-aligned as needed.
+pub fn to_ascii(src: &str) -> String {
+    convert(src, |symbol| (symbol.unicode, symbol.ascii[0]))
+}
+
+/// Nodes collected from the parse tree which affect the conversion.
+#[derive(Default)]
+struct Collected<'tree> {
+    /// Symbols to replace, in source order, mapped to their replacement.
+    replacements: Vec<(Node<'tree>, &'static str)>,
+    /// Conjunction & disjunction lists, in pre-order.
+    jlists: Vec<Node<'tree>>,
+    /// Rows starting inside a string or erroneous text, which should not be
+    /// re-indented.
+    frozen_rows: Vec<usize>,
+}
+
+fn convert(
+    src: &str,
+    spellings: impl Fn(&Symbol) -> (&'static [&'static str], &'static str),
+) -> String {
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&crate::language()).unwrap();
+    let Some(tree) = parser.parse(src, None) else {
+        return src.to_string();
+    };
+
+    let symbols = symbol_table();
+    let mut collected = Collected::default();
+    collect(&tree, src, &symbols, &spellings, &mut collected);
+
+    let lines = Lines::new(src, &tree, &collected);
+    let mut indent_delta = vec![0isize; lines.starts.len()];
+    for jlist in &collected.jlists {
+        let Some(first) = jlist_first_bullet(jlist) else {
+            continue;
+        };
+        let row = first.start_position().row;
+        let old_col = lines.old_col(first.start_byte()) as isize;
+        let new_col = lines.new_col(first.start_byte(), indent_delta[row]) as isize;
+        let delta = new_col - old_col;
+        for delta_row in indent_delta
+            .iter_mut()
+            .take(last_token_row(*jlist) + 1)
+            .skip(row + 1)
+        {
+            *delta_row = delta;
+        }
+    }
+
+    let mut edits: Vec<_> = collected
+        .replacements
+        .iter()
+        .map(|(node, to)| (node.byte_range(), to.to_string()))
+        .collect();
+    for (row, &delta) in indent_delta.iter().enumerate() {
+        if delta != 0 {
+            edits.extend(lines.lead_edits(row, delta));
+        }
+    }
+    edits.sort_by_key(|(range, _)| (range.start, range.end));
+    // Error recovery can leave one symbol token under two nodes.
+    edits.dedup_by(|(later, _), (earlier, _)| later.start < earlier.end);
+
+    render(src, &edits)
+}
+
+fn symbol_table() -> HashMap<&'static str, &'static Symbol> {
+    SYMBOLS.iter().map(|s| (s.kind, s)).collect()
+}
+
+/// Whether the node is text, like a comment or string, whose contents are
+/// never treated as symbols.
+fn is_opaque(node: Node) -> bool {
+    let kind = node.kind_id();
+    kind == node_kinds::COMMENT.id()
+        || kind == node_kinds::BLOCK_COMMENT.id()
+        || kind == node_kinds::STRING.id()
+        || node.is_error()
+}
+
+/// The spelling of operators and other symbols used in a spec.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolStyle {
+    /// All symbols are spelled in ASCII, or the spec contains no symbols
+    /// with a Unicode spelling.
+    Ascii,
+    /// All symbols with a Unicode spelling are spelled in Unicode.
+    Unicode,
+    /// Both spellings are used.
+    Mixed,
+}
+
+/// Determine whether the symbols of the spec are spelled in ASCII, Unicode,
+/// or a mix of both. Only symbol tokens are inspected, so Unicode text in
+/// comments or strings does not count.
+pub fn symbol_style(tree: &Tree, src: &[u8]) -> SymbolStyle {
+    let symbols = symbol_table();
+    let (mut ascii, mut unicode) = (false, false);
+    walk(tree.root_node(), |node| {
+        if is_opaque(node) {
+            return false;
+        }
+        let Some(symbol) = symbols.get(node.kind()) else {
+            return true;
+        };
+        let text = std::str::from_utf8(&src[node.byte_range()]).unwrap_or_default();
+        ascii |= symbol.ascii.contains(&text);
+        unicode |= symbol.unicode.contains(&text);
+        false
+    });
+    match (ascii, unicode) {
+        (true, true) => SymbolStyle::Mixed,
+        (false, true) => SymbolStyle::Unicode,
+        _ => SymbolStyle::Ascii,
+    }
+}
+
+/// Walks the tree collecting symbols to replace and jlists to re-align.
+fn collect<'tree>(
+    tree: &'tree Tree,
+    src: &str,
+    symbols: &HashMap<&str, &Symbol>,
+    spellings: &impl Fn(&Symbol) -> (&'static [&'static str], &'static str),
+    collected: &mut Collected<'tree>,
+) {
+    walk(tree.root_node(), |node| {
+        let kind = node.kind_id();
+        if is_opaque(node) {
+            if kind != node_kinds::BLOCK_COMMENT.id() {
+                let start = node.start_position().row;
+                let end = node.end_position().row;
+                collected.frozen_rows.extend(start + 1..=end);
+            }
+            return false;
+        } else if let Some(symbol) = symbols.get(node.kind()) {
+            let (from, to) = spellings(symbol);
+            if from.contains(&&src[node.byte_range()]) {
+                collected.replacements.push((node, to));
+            }
+            return false;
+        } else if kind == node_kinds::CONJ_LIST.id() || kind == node_kinds::DISJ_LIST.id() {
+            collected.jlists.push(node);
+        }
+        true
+    });
+}
+
+/// Gets the bullet symbol of the first item of a conjunction or disjunction
+/// list.
+fn jlist_first_bullet<'tree>(jlist: &Node<'tree>) -> Option<Node<'tree>> {
+    jlist.named_child(0)?.named_child(0)
+}
+
+/// Gets the row on which the last token of a node ends. A jlist itself may
+/// end later, at the start of the token which terminated it, and may contain
+/// trailing comments belonging to the lines after it.
+fn last_token_row(node: Node) -> usize {
+    let mut node = node;
+    loop {
+        let mut cursor = node.walk();
+        let last = node
+            .children(&mut cursor)
+            .filter(|child| !child.is_extra() && child.end_byte() > child.start_byte())
+            .last();
+        match last {
+            Some(child) => node = child,
+            None => return node.end_position().row,
+        }
+    }
+}
+
+/// The width of a tab stop, as the scanner counts columns.
+const TAB_WIDTH: usize = 8;
+
+/// Gets the column following a character at the given column, with tabs
+/// advancing to the next tab stop as in the scanner.
+fn next_column(col: usize, c: char) -> usize {
+    if c == '\t' {
+        col + TAB_WIDTH - col % TAB_WIDTH
+    } else {
+        col + 1
+    }
+}
+
+/// Gets the column reached by the given text, starting from `col`.
+fn width(col: usize, text: &str) -> usize {
+    text.chars().fold(col, next_column)
+}
+
+/// Gets the prefix of the whitespace `ws`, starting at column `col`, that
+/// stays within the `target` column, padded with spaces to reach it. Tabs
+/// are kept where they fit, so tab indentation survives re-alignment.
+fn fit(ws: &str, col: usize, target: usize) -> String {
+    let mut fitted = String::new();
+    let mut col = col;
+    for c in ws.chars() {
+        let next = next_column(col, c);
+        if next > target {
+            break;
+        }
+        fitted.push(c);
+        col = next;
+    }
+    fitted.extend(std::iter::repeat_n(' ', target.saturating_sub(col)));
+    fitted
+}
+
+/// Render the source with each byte range in `edits`, which must be sorted
+/// and disjoint, replaced by its text.
+fn render(src: &str, edits: &[(Range<usize>, String)]) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut pos = 0;
+    for (range, to) in edits {
+        out.push_str(&src[pos..range.start]);
+        out.push_str(to);
+        pos = range.end;
+    }
+    out.push_str(&src[pos..]);
+    out
+}
+
+/// The whitespace before the first token of a row, which can be rewritten to
+/// shift that token.
+struct Lead {
+    /// The whitespace indenting the row, unless it starts inside a comment.
+    indent: Option<Range<usize>>,
+    /// The whitespace between the last comment before the token & it, if any
+    /// comment precedes the token.
+    gap: Option<Range<usize>>,
+    /// The byte offset of the token.
+    token: usize,
+}
+
+/// Line & column bookkeeping for the source being converted.
+struct Lines<'a> {
+    /// Byte offset of the start of each row.
+    starts: Vec<usize>,
+    /// Per-row list of the replacements on that row, in source order.
+    replacements: Vec<Vec<(Range<usize>, &'static str)>>,
+    /// Whether each row must not be re-indented.
+    frozen: Vec<bool>,
+    text: &'a str,
+    tree: &'a Tree,
+}
+
+impl<'a> Lines<'a> {
+    fn new(src: &'a str, tree: &'a Tree, collected: &Collected) -> Self {
+        let mut starts = vec![0];
+        starts.extend(src.match_indices('\n').map(|(i, _)| i + 1));
+        let mut replacements = vec![Vec::new(); starts.len()];
+        for (node, to) in &collected.replacements {
+            replacements[node.start_position().row].push((node.byte_range(), *to));
+        }
+        let mut frozen = vec![false; starts.len()];
+        for &row in &collected.frozen_rows {
+            frozen[row] = true;
+        }
+        Lines {
+            starts,
+            replacements,
+            frozen,
+            text: src,
+            tree,
+        }
+    }
+
+    fn end(&self, row: usize) -> usize {
+        self.starts.get(row + 1).copied().unwrap_or(self.text.len())
+    }
+
+    /// Find the whitespace before the first token of a row, skipping any
+    /// block comments before it, including one the row starts inside. Rows
+    /// with no token or which must not be re-indented have none.
+    fn lead(&self, row: usize) -> Option<Lead> {
+        if self.frozen[row] {
+            return None;
+        }
+        let (start, end) = (self.starts[row], self.end(row));
+        let skip_blanks = |pos: usize| {
+            let rest = &self.text[pos..end];
+            end - rest.trim_start_matches([' ', '\t']).len()
+        };
+        let mut indent = None;
+        let mut pos = match outermost_comment(self.tree, start) {
+            Some(comment) if comment.start_byte() < start => comment.end_byte(),
+            _ => {
+                indent = Some(start..skip_blanks(start));
+                start
+            }
+        };
+        let mut after_comment = indent.is_none();
+        loop {
+            if pos > end {
+                return None;
+            }
+            let token = skip_blanks(pos);
+            let rest = &self.text[token..end];
+            if rest.is_empty() || rest.starts_with(['\r', '\n']) {
+                return None;
+            } else if !rest.starts_with("(*") {
+                let gap = after_comment.then_some(pos..token);
+                return Some(Lead { indent, gap, token });
+            }
+            pos = outermost_comment(self.tree, token)?.end_byte();
+            after_comment = true;
+        }
+    }
+
+    /// Get the edits shifting the first token of a row by `delta` columns,
+    /// taking the shift from the indentation first, then from the gap after
+    /// any comments before the token.
+    fn lead_edits(&self, row: usize, delta: isize) -> Vec<(Range<usize>, String)> {
+        let Some(lead) = self.lead(row) else {
+            return Vec::new();
+        };
+        let start = self.starts[row];
+        let target = (self.old_col(lead.token) as isize + delta).max(0) as usize;
+        let mut edits = Vec::new();
+        let mut col = 0;
+        let mut pos = start;
+        if let Some(indent) = lead.indent {
+            let ws = &self.text[indent.clone()];
+            let indent_target = (width(0, ws) as isize + delta).max(0) as usize;
+            let fitted = fit(ws, 0, indent_target.min(target));
+            col = width(0, &fitted);
+            pos = indent.end;
+            edits.push((indent, fitted));
+        }
+        if let Some(gap) = lead.gap {
+            col = width(col, &self.text[pos..gap.start]);
+            let fitted = fit(&self.text[gap.clone()], col, target);
+            edits.push((gap, fitted));
+        }
+        edits
+    }
+
+    fn row(&self, byte: usize) -> usize {
+        self.starts.partition_point(|&start| start <= byte) - 1
+    }
+
+    /// The column of the given byte offset in the original source.
+    fn old_col(&self, byte: usize) -> usize {
+        width(0, &self.text[self.starts[self.row(byte)]..byte])
+    }
+
+    /// The column of the given byte offset after replacing symbols, with its
+    /// row shifted by `delta` columns.
+    fn new_col(&self, byte: usize, delta: isize) -> usize {
+        let row = self.row(byte);
+        let mut edits = if delta != 0 {
+            self.lead_edits(row, delta)
+        } else {
+            Vec::new()
+        };
+        edits.extend(
+            self.replacements[row]
+                .iter()
+                .filter(|(range, _)| range.end <= byte)
+                .map(|(range, to)| (range.clone(), to.to_string())),
+        );
+        edits.sort_by_key(|(range, _)| (range.start, range.end));
+        edits.dedup_by(|(later, _), (earlier, _)| later.start < earlier.end);
+        let start = self.starts[row];
+        let mut col = 0;
+        let mut pos = start;
+        for (range, to) in &edits {
+            col = width(width(col, &self.text[pos..range.start]), to);
+            pos = range.end;
+        }
+        width(col, &self.text[pos..byte])
+    }
+}
+
+/// Gets the outermost block comment containing the given byte.
+fn outermost_comment(tree: &Tree, byte: usize) -> Option<Node<'_>> {
+    let mut node = tree.root_node().descendant_for_byte_range(byte, byte + 1)?;
+    let mut comment = None;
+    loop {
+        if node.kind_id() == node_kinds::BLOCK_COMMENT.id() {
+            comment = Some(node);
+        }
+        match node.parent() {
+            Some(parent) => node = parent,
+            None => return comment,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASCII: &str = r#"---- MODULE Test ----
+EXTENDS Naturals
+CONSTANT S
+VARIABLE x
+\* \in and == are left alone in comments
+Init == x \in S /\ "x \in S" # ""
+Next == /\ \A s \in S : s >= 0
+        /\ \/ x' = [s \in S |-> s]
+           \/ UNCHANGED <<x>>
+        /\ x \subseteq S
+Spec == Init /\ [][Next]_<<x>>
+(* (* nested *) x \in S *)
+====
+"#;
+
+    const UNICODE: &str = r#"---- MODULE Test ----
+EXTENDS Naturals
+CONSTANT S
+VARIABLE x
+\* \in and == are left alone in comments
+Init ≜ x ∈ S ∧ "x \in S" ≠ ""
+Next ≜ ∧ ∀ s ∈ S : s ≥ 0
+       ∧ ∨ x' = [s ∈ S ↦ s]
+         ∨ UNCHANGED ⟨x⟩
+       ∧ x ⊆ S
+Spec ≜ Init ∧ □[Next]_⟨x⟩
+(* (* nested *) x \in S *)
+====
+"#;
+
+    #[test]
+    fn test_to_unicode() {
+        assert_eq!(to_unicode(ASCII), UNICODE);
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        assert_eq!(to_ascii(UNICODE), ASCII.replace(" # ", " /= "));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let ascii = to_ascii(UNICODE);
+        assert_eq!(to_ascii(&to_unicode(&ascii)), ascii);
+        assert_eq!(to_unicode(&to_ascii(UNICODE)), UNICODE);
+    }
+
+    #[test]
+    fn test_mixed_def_eq() {
+        let mixed = "---- MODULE Test ----\na == 1\nb ≜ 2\nf[x \\in S] ≜ x\nM ≜ INSTANCE N\n====\n";
+        let ascii =
+            "---- MODULE Test ----\na == 1\nb == 2\nf[x \\in S] == x\nM == INSTANCE N\n====\n";
+        let unicode = "---- MODULE Test ----\na ≜ 1\nb ≜ 2\nf[x ∈ S] ≜ x\nM ≜ INSTANCE N\n====\n";
+        assert_eq!(to_ascii(mixed), ascii);
+        assert_eq!(to_unicode(mixed), unicode);
+        assert_eq!(to_unicode(&to_ascii(mixed)), unicode);
+    }
+
+    #[test]
+    fn test_alternate_unicode_spellings() {
+        assert_eq!(to_ascii("op ≜ P ⤳ Q ∧ ⋄R"), "op == P ~> Q /\\ <>R");
+        assert_eq!(to_unicode("op == P ~> Q"), "op ≜ P ↝ Q");
+    }
+
+    #[test]
+    fn test_symbol_style() {
+        let style =
+            |src: &str| symbol_style(&crate::parse_with(src, true).unwrap(), src.as_bytes());
+        assert_eq!(style(ASCII), SymbolStyle::Ascii);
+        assert_eq!(style(UNICODE), SymbolStyle::Unicode);
+        assert_eq!(style("op ≜ \\A x ∈ S : x >= 0"), SymbolStyle::Mixed);
+        assert_eq!(style("op == \"∀ ∈ ≜\" \\* ∃"), SymbolStyle::Ascii);
+        assert_eq!(style(""), SymbolStyle::Ascii);
+    }
+
+    #[test]
+    fn test_nested_and_comment_prefixed_jlists() {
+        let specs = [
+            "---- MODULE T ----\nop == \\/ /\\ \\A y \\in T : \\/ c\n                         \\/ d\n      \\/ e\n====\n",
+            "---- MODULE T ----\nop == /\\ a \\in S\n  (* c *)   /\\ b \\in S\n====\n",
+            "---- MODULE T ----\nop == \\A x \\in S : /\\ a\n(* c *)            /\\ b\n====\n",
+            "---- MODULE T ----\nop == /\\ \\/ a \\in S\n         \\/ b\n      /\\ c\n\\* done\nnext == 1\n====\n",
+        ];
+        for ascii in specs {
+            assert_round_trip(ascii);
+        }
+    }
+
+    #[test]
+    fn test_tab_indented_jlists() {
+        let specs = [
+            "---- MODULE T ----\nop ==\n\t/\\ a\n\t/\\ \\/ b\n\t   \\/ c\n\t/\\ d\n====\n",
+            "---- MODULE T ----\nop ==\t/\\ a\n\t/\\ b\n        /\\ c\n====\n",
+            "---- MODULE T ----\nop == (*\tc *) /\\ a\n\t\t     /\\ b\n====\n",
+            "---- MODULE T ----\nop == /\\ a\n   \t/\\ \\/ b \\in S\n\t   \\/ c\n====\n",
+        ];
+        for ascii in specs {
+            assert_round_trip(ascii);
+        }
+        let unicode = to_unicode(specs[0]);
+        assert!(unicode.contains("\n\t∧ ∨ b\n\t  ∨ c\n"), "{unicode}");
+        assert_eq!(to_unicode("op ==\t/\\ a\n\t/\\ b"), "op ≜\t∧ a\n\t∧ b");
+    }
+
+    #[test]
+    fn test_jlist_after_multiline_comment() {
+        let ascii =
+            "---- MODULE T ----\nop == (*x\nx*) /\\ 1 (*x\nx*) /\\ \\/ 2 (*x\nxxxx*) \\/ 3\n====\n";
+        assert_round_trip(ascii);
+    }
+
+    /// Asserts that converting the spec to Unicode and back leaves its parse
+    /// tree unchanged.
+    fn assert_round_trip(ascii: &str) {
+        let sexp = |src: &str| crate::parse_with(src, true).unwrap().root_node().to_sexp();
+        let expected = sexp(ascii);
+        assert!(!expected.contains("ERROR"), "{ascii}");
+        let unicode = to_unicode(ascii);
+        assert_eq!(sexp(&unicode), expected, "{unicode}");
+        let back = to_ascii(&unicode);
+        assert_eq!(sexp(&back), expected, "{back}");
+        assert!(back.ends_with("\n====\n"), "{back}");
+    }
+
+    #[test]
+    fn test_jlist_structure_preserved() {
+        let ascii = crate::parse_with(ASCII, true).unwrap();
+        let unicode = crate::parse_with(&to_unicode(ASCII), true).unwrap();
+        assert!(!unicode.root_node().has_error());
+        let shape = |tree: &Tree| {
+            crate::named_descendants(tree)
+                .map(|node| node.kind())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(shape(&ascii), shape(&unicode));
+    }
+}
@@ -0,0 +1,149 @@
+//! Iteration over the named nodes of a tree.
+
+use std::iter;
+
+use tree_sitter::{Node, Tree, TreeCursor};
+
+/// Iterator returned by [named_descendants].
+struct NamedDescendants<'tree> {
+    cursor: TreeCursor<'tree>,
+    done: bool,
+}
+
+impl<'tree> Iterator for NamedDescendants<'tree> {
+    type Item = Node<'tree>;
+
+    fn next(&mut self) -> Option<Node<'tree>> {
+        while !self.done {
+            let node = self.cursor.node();
+            if !self.cursor.goto_first_child() {
+                while !self.cursor.goto_next_sibling() {
+                    if !self.cursor.goto_parent() {
+                        self.done = true;
+                        break;
+                    }
+                }
+            }
+            if node.is_named() {
+                return Some(node);
+            }
+        }
+        None
+    }
+}
+
+/// Get every named node in the tree, starting with the root, in pre-order.
+/// Anonymous nodes like punctuation and keywords are skipped, but their named
+/// descendants are not.
+pub fn named_descendants(tree: &Tree) -> impl Iterator<Item = Node<'_>> {
+    NamedDescendants {
+        cursor: tree.walk(),
+        done: false,
+    }
+}
+
+/// Visit every node below and including `node` in pre-order, named or not.
+/// The children of a node are only visited if `visit` returns true for it.
+pub(crate) fn walk<'tree>(node: Node<'tree>, mut visit: impl FnMut(Node<'tree>) -> bool) {
+    let mut cursor = node.walk();
+    loop {
+        if visit(cursor.node()) && cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return;
+            }
+        }
+    }
+}
+
+/// Get the named children of `node`, without the caller having to provide a
+/// [TreeCursor].
+pub fn named_children(node: Node) -> impl Iterator<Item = Node> {
+    iter::successors(node.named_child(0), |child| child.next_named_sibling())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_descendants() {
+        let code =
+            "---- MODULE Test ----\nEXTENDS Naturals\nop(x) == [y \\in {x} |-> y + 1]\n====\n";
+        let tree = crate::parse_with(code, true).unwrap();
+        let mut expected = Vec::new();
+        walk(tree.root_node(), |node| {
+            if node.is_named() {
+                expected.push(node);
+            }
+            true
+        });
+        let nodes: Vec<_> = named_descendants(&tree).collect();
+        assert_eq!(nodes, expected);
+        assert_eq!(nodes[0], tree.root_node());
+        assert!(nodes.iter().any(|node| node.kind() == "identifier_ref"));
+    }
+
+    #[test]
+    fn test_named_descendants_of_empty_tree() {
+        let tree = crate::parse_with("", true).unwrap();
+        let nodes: Vec<_> = named_descendants(&tree).collect();
+        assert_eq!(nodes, [tree.root_node()]);
+    }
+
+    #[test]
+    fn test_walk_skips_subtrees() {
+        let code = "---- MODULE Test ----\nop == {1, 2}\nnext == 3\n====\n";
+        let tree = crate::parse_with(code, true).unwrap();
+        let mut kinds = Vec::new();
+        walk(tree.root_node(), |node| {
+            kinds.push(node.kind());
+            node.kind() != "operator_definition"
+        });
+        assert_eq!(
+            kinds,
+            [
+                "source_file",
+                "module",
+                "header_line",
+                "----",
+                "MODULE",
+                "identifier",
+                "header_line",
+                "----",
+                "operator_definition",
+                "operator_definition",
+                "double_line"
+            ]
+        );
+        let module = tree.root_node().named_child(0).unwrap();
+        let mut numbers = 0;
+        walk(module.named_child(3).unwrap(), |node| {
+            numbers += usize::from(node.kind() == "nat_number");
+            true
+        });
+        assert_eq!(numbers, 2);
+    }
+
+    #[test]
+    fn test_named_children() {
+        let tree = crate::parse_with("---- MODULE Test ----\nop == 1\n====\n", true).unwrap();
+        let module = tree.root_node().named_child(0).unwrap();
+        let kinds: Vec<_> = named_children(module).map(|node| node.kind()).collect();
+        assert_eq!(
+            kinds,
+            [
+                "header_line",
+                "identifier",
+                "header_line",
+                "operator_definition",
+                "double_line"
+            ]
+        );
+        let definition = module.named_child(3).unwrap();
+        let number = definition.child_by_field_name("definition").unwrap();
+        assert_eq!(named_children(number).count(), 0);
+    }
+}
@@ -92,6 +92,8 @@ module.exports = grammar({
     $._notify_pcal_algorithm_start,
     $._notify_pcal_algorithm_end,
     $._double_excl,
+    $._block_comment_text,
+    $._column_offset,
     $._error_sentinel
   ],
 
@@ -110,7 +112,10 @@ module.exports = grammar({
   extras: $ => [
     /\s|\r?\n/,
     $.comment,
+    $.pcal_translation_start,
+    $.pcal_translation_end,
     $.block_comment,
+    $._column_offset,
   ],
 
   // Prefix, infix, and postfix operator precedence categories, defined
@@ -146,6 +151,9 @@ module.exports = grammar({
     // Lookahead to disambiguate '{'  identifier  •  '\in'  …
     // Matches both finite_set_literal and set_filter
     [$._expr, $.restricted_quantifier_bound],
+    // Lookahead to disambiguate '{'  'Nat'  •  '\in'  …
+    // Same as above, with a standard number set as the identifier
+    [$._primitive_value_set, $.restricted_quantifier_bound],
     // Lookahead to disambiguate '['  langle_bracket  identifier  •  '>>'  …
     // Matches step_expr_or_stutter and function_literal
     [$._expr, $.tuple_of_identifiers],
@@ -155,9 +163,13 @@ module.exports = grammar({
     // Lookahead to disambiguate identifier  '('  identifier  •  ','  …
     // Could be op(a, b) == ... (decl'n) or op(a, b) (expression) or label
     [$._expr, $._id_or_op_declaration, $.label],
+    // Same as above with a standard number set as the op, so not a label
+    [$._expr, $._id_or_op_declaration],
     // Lookahead to disambiguate identifier  •  '['  …
     // Could be f[x \in S] == ... (function def'n) or f[x] (application)
     [$._expr, $.function_definition],
+    // Same as above, with a standard number set as the identifier
+    [$._primitive_value_set, $.function_definition],
     // Lookahead to disambiguate subexpr_component  '!'  •  '\in'  …
     // The '\in' could be followed by a ! or it could be the end
     [$.subexpr_prefix],
@@ -170,12 +182,13 @@ module.exports = grammar({
   ],
 
   rules: {
-    // Can be one of three things:
+    // Can be one of these things:
     // * a valid TLA⁺ source file with an encapsulating module
     // * a source file containing multiple modules (ambiguously valid but used by the tools)
     // * a TLA⁺ snippet without an encapsulating module
     // * a PlusCal algorithm without an encapsulating module
-    source_file: $ => choice(
+    // * nothing but whitespace and comments
+    source_file: $ => optional(choice(
       seq(
         optional(alias($.leading_extramodular_text, $.extramodular_text)),
         repeat1(prec(1, seq($.module, optional(alias($.trailing_extramodular_text, $.extramodular_text)))))
@@ -185,10 +198,15 @@ module.exports = grammar({
         repeat1($._unit)
       ),
       $.pcal_algorithm
-    ),
+    )),
 
     // \* this is a comment ending with newline
-    comment: $ => token(prec(PREC.COMMENT, /\\\*.*/)),
+    comment: $ => token(prec(PREC.COMMENT, /\\\*[^\r\n]*/)),
+
+    // \* BEGIN TRANSLATION and \* END TRANSLATION comments, which enclose
+    // the TLA⁺ translation of a PlusCal algorithm
+    pcal_translation_start: $ => token(prec(PREC.PCAL, /\\\*[ \t]*BEGIN TRANSLATION[^\r\n]*/)),
+    pcal_translation_end: $ => token(prec(PREC.PCAL, /\\\*[ \t]*END TRANSLATION[^\r\n]*/)),
 
     // source: https://github.com/tree-sitter/tree-sitter/discussions/1252#discussioncomment-988725
     block_comment: $ => seq(
@@ -196,26 +214,16 @@ module.exports = grammar({
       repeat(
         choice(
           $.pcal_algorithm,
+          $.pcal_options,
           $.block_comment_text
         )
       ),
       token(prec(PREC.BLOCK_COMMENT, '*)'))
     ),
 
-    block_comment_text: $ =>
-      prec.right(repeat1(
-        choice(
-          token(prec(PREC.BLOCK_COMMENT, regexOr(
-            '[^*()]', // any symbol except reserved
-            '[^*][)]', // closing parenthesis, which is not a comment end
-            '[(][^(*]', // opening parenthesis, which is not a comment start
-            '[*][)][ \t]*(\r\n|\n)?[ \t]*[(][*]' // contiguous block comment border
-          ))),
-          token(prec(PREC.BLOCK_COMMENT, /\*/)),
-          token(prec(PREC.BLOCK_COMMENT, /\(/)),
-          token(prec(PREC.BLOCK_COMMENT, /\)/)),
-        )
-      )),
+    // Text is lexed by the external scanner so that an unterminated block
+    // comment can stop at the next module boundary for error recovery.
+    block_comment_text: $ => prec.right(repeat1($._block_comment_text)),
 
     // Top-level module declaration
     module: $ => seq(
@@ -223,15 +231,20 @@ module.exports = grammar({
       'MODULE', field('name', $.identifier),
       alias($.single_line, $.header_line),
       optional($.extends),
-      repeat($._unit),
+      optional($._module_body),
       $.double_line
     ),
 
+    // Units are left-recursive rather than a repeat so that a missing ====
+    // can be recovered from. Between units of a repeat the parser can only
+    // reduce before the terminator, so it never tries inserting one.
+    _module_body: $ => choice($._unit, seq($._module_body, $._unit)),
+
     // Line of ---------- of length at least 4
     single_line: $ => seq('----', token.immediate(/-*/)),
 
     // Line of =========== of length at least 4
-    double_line: $ => seq('====', token.immediate(/=*/)),
+    double_line: $ => token(seq('====', /=*/)),
 
     // Various syntactic elements and their unicode equivalents
     def_eq:             $ => choice('==', '≜'),
@@ -332,9 +345,13 @@ module.exports = grammar({
     // op, op(_,_), _+_, etc.
     operator_declaration: $ => choice(
       arity1OrN($.identifier, $.placeholder),
-      seq(field('name', $.prefix_op_symbol), $.placeholder),
-      seq($.placeholder, field('name', $.infix_op_symbol), $.placeholder),
-      seq($.placeholder, field('name', $.postfix_op_symbol))
+      seq(field('name', $.prefix_op_symbol), field('parameter', $.placeholder)),
+      seq(
+        field('parameter', $.placeholder),
+        field('name', $.infix_op_symbol),
+        field('parameter', $.placeholder)
+      ),
+      seq(field('parameter', $.placeholder), field('name', $.postfix_op_symbol))
     ),
 
     // Either an identifier or an operator declaration
@@ -371,8 +388,8 @@ module.exports = grammar({
 
     // f[x \in Nat] == 2*x
     function_definition: $ => seq(
-      field('name', $.identifier),
-      '[', commaList1($.quantifier_bound), ']',
+      field('name', choice($.identifier, $._number_set)),
+      '[', field('bound', commaList1($.quantifier_bound)), ']',
       $.def_eq,
       field('definition', $._expr)
     ),
@@ -386,14 +403,14 @@ module.exports = grammar({
 
     // x <- y, w <- z
     substitution: $ => seq(
-      choice(
+      field('target', choice(
         alias($.identifier, $.identifier_ref),
         $.prefix_op_symbol,
         $.infix_op_symbol,
         $.postfix_op_symbol
-      ),
+      )),
       $.gets,
-      $._op_or_expr
+      field('expression', $._op_or_expr)
     ),
 
     // Either an operator (op, +, *, LAMBDA, etc.) or an expression
@@ -416,6 +433,7 @@ module.exports = grammar({
     // Can either bind parameters to the op or leave them unbound
     subexpr_component: $ => choice(
       alias($.identifier, $.identifier_ref),
+      $._number_set,
       $.bound_op,
       $.bound_nonfix_op,
       $.prefix_op_symbol,
@@ -424,7 +442,10 @@ module.exports = grammar({
     ),
 
     // f(a, op, b)
-    bound_op: $ => arity1OrN(alias($.identifier, $.identifier_ref), $._op_or_expr),
+    bound_op: $ => arity1OrN(
+      choice(alias($.identifier, $.identifier_ref), $._number_set),
+      $._op_or_expr
+    ),
 
     // +(2, 4)
     bound_nonfix_op: $ => choice(
@@ -679,7 +700,7 @@ module.exports = grammar({
 
     // A quantifier bound allowing only one introduced element
     restricted_quantifier_bound: $ => seq(
-      field('intro', choice($.identifier, $.tuple_of_identifiers)),
+      field('intro', choice($.identifier, $._number_set, $.tuple_of_identifiers)),
       $.set_in,
       field('set', $._expr)
     ),
@@ -698,7 +719,11 @@ module.exports = grammar({
 
     // [n \in Nat |-> 2*n]
     function_literal: $ => seq(
-      '[', commaList1($.quantifier_bound), $.all_map_to, $._expr, ']'
+      '[',
+      field('bound', commaList1($.quantifier_bound)),
+      $.all_map_to,
+      field('expression', $._expr),
+      ']'
     ),
 
     // [Nat -> Nat]
@@ -708,12 +733,12 @@ module.exports = grammar({
 
     // [foo |-> 0, bar |-> 1]
     record_literal: $ => seq(
-      '[', commaList1(seq($.identifier, $.all_map_to, $._expr)), ']'
+      '[', commaList1(seq(choice($.identifier, $._number_set), $.all_map_to, $._expr)), ']'
     ),
 
     // [foo : {0, 1}, bar : {0, 1}]
     set_of_records: $ => seq(
-      '[', commaList1(seq($.identifier, ':', $._expr)), ']'
+      '[', commaList1(seq(choice($.identifier, $._number_set), ':', $._expr)), ']'
     ),
 
     // r.val
@@ -761,20 +786,22 @@ module.exports = grammar({
 
     // [x ' > x]_<<x>>
     step_expr_or_stutter: $ => seq(
-      '[', $._expr, ']_', $._subscript_expr
+      '[', field('action', $._expr), ']_', field('subscript', $._subscript_expr)
     ),
 
     // <<x' > x>>_<<x>>
     step_expr_no_stutter: $ => seq(
       $.langle_bracket,
-      $._expr,
+      field('action', $._expr),
       $.rangle_bracket_sub,
-      $._subscript_expr
+      field('subscript', $._subscript_expr)
     ),
 
     // WF_vars(ActionName)
     fairness: $ => seq(
-      choice('WF_', 'SF_'), $._subscript_expr, '(', $._expr, ')'
+      choice('WF_', 'SF_'),
+      field('subscript', $._subscript_expr),
+      '(', field('action', $._expr), ')'
     ),
 
     // IF a > b THEN a ELSE b
@@ -798,10 +825,12 @@ module.exports = grammar({
     )),
 
     // p -> val
-    case_arm: $ => prec('0-0', seq($._expr, $.case_arrow, $._expr)),
+    case_arm: $ => prec('0-0', seq(
+      field('condition', $._expr), $.case_arrow, field('value', $._expr)
+    )),
 
     // OTHER -> val
-    other_arm: $ => prec('0-0', seq('OTHER', $.case_arrow, $._expr)),
+    other_arm: $ => prec('0-0', seq('OTHER', $.case_arrow, field('value', $._expr))),
 
     // LET x == 5 IN 2*x
     let_in: $ => prec('0-0', seq(
@@ -869,8 +898,9 @@ module.exports = grammar({
     // Precedence defined on p271 of Specifying Systems.
     bound_prefix_op: $ => choice(
       prefixOpPrec('4-4',   $._expr,  $.lnot),
+      prefixOpPrec('8-8',   $._expr, choice($.powerset, $.union)),
+      prefixOpPrec('9-9',   $._expr, $.domain),
       prefixOpPrec('12-12', $._expr, $.negative),
-      prefixOpPrec('13-13', $._expr, choice($.powerset, $.union, $.domain)),
       prefixOpPrec('15-15', $._expr, choice(
         $.enabled, $.unchanged, $.always, $.eventually))
     ),
@@ -880,7 +910,7 @@ module.exports = grammar({
     plus_arrow:       $ => choice('-+->', '⇸', '⥅'),
     equiv:            $ => choice('\\equiv', '≡'),
     iff:              $ => choice('<=>', '⟺', '⇔'),
-    leads_to:         $ => choice('~>', '⇝', '↝'),
+    leads_to:         $ => choice('~>', '⇝', '↝', '⤳'),
     land:             $ => choice('/\\', '\\land', '∧'),
     lor:              $ => choice('\\/', '\\lor', '∨'),
     assign:           $ => choice(':=', '≔'),
@@ -1056,14 +1086,14 @@ module.exports = grammar({
 
     // ASSUME C \in Nat
     assumption: $ => seq(
-      choice('ASSUME', 'ASSUMPTION', 'AXIOM'),
+      field('keyword', choice('ASSUME', 'ASSUMPTION', 'AXIOM')),
       optional(seq(field('name', $.identifier), $.def_eq)),
-      $._expr
+      field('statement', $._expr)
     ),
 
     // THEOREM Spec => []Safety
     theorem: $ => seq(
-      choice('THEOREM', 'PROPOSITION', 'LEMMA', 'COROLLARY'),
+      field('keyword', choice('THEOREM', 'PROPOSITION', 'LEMMA', 'COROLLARY')),
       optional(seq(field('name', $.identifier), $.def_eq)),
       field('statement', choice($._expr, $.assume_prove)),
       field('proof', optional($._proof))
@@ -1107,8 +1137,8 @@ module.exports = grammar({
       optional('PROOF'),
       choice(
         seq('BY', optional('ONLY'), $.use_body),
-        'OBVIOUS',
-        'OMITTED'
+        alias('OBVIOUS', $.obvious),
+        alias('OMITTED', $.omitted)
       )
     ),
 
@@ -1152,12 +1182,16 @@ module.exports = grammar({
     witness_proof_step: $ => seq('WITNESS', commaList1($._expr)),
     take_proof_step: $ => seq('TAKE', $._bound_or_identifier_list),
     suffices_proof_step: $ => seq(
-      optional('SUFFICES'), choice($._expr, $.assume_prove), optional($._proof)
+      optional('SUFFICES'),
+      field('statement', choice($._expr, $.assume_prove)),
+      field('proof', optional($._proof))
+    ),
+    case_proof_step: $ => seq(
+      'CASE', field('statement', $._expr), field('proof', optional($._proof))
     ),
-    case_proof_step: $ => seq('CASE', $._expr, optional($._proof)),
     pick_proof_step: $ => seq(
-      'PICK', $._bound_or_identifier_list, ':', $._expr,
-      optional($._proof)
+      'PICK', $._bound_or_identifier_list, ':', field('statement', $._expr),
+      field('proof', optional($._proof))
     ),
 
     // One of:
@@ -1173,7 +1207,7 @@ module.exports = grammar({
       $._begin_proof_step,
       $.proof_step_id,
       'QED',
-      optional($._proof)
+      field('proof', optional($._proof))
     ),
 
     use_or_hide: $ => seq(
@@ -1264,13 +1298,19 @@ module.exports = grammar({
       seq(
         choice(
           token(prec(PREC.PCAL, '--algorithm')),
-          $.fair
+          field('fairness', $.fair)
         ),
         $._notify_pcal_algorithm_start
       ),
 
     fair: $ => seq(token(prec(PREC.PCAL, '--fair')), 'algorithm'),
 
+    // PlusCal options (-termination -sf)
+    pcal_options: $ => seq(
+      token(prec(PREC.PCAL, 'PlusCal')), 'options',
+      '(', field('options', alias(/[^)]+/, $.value)), ')'
+    ),
+
     // Operators, which depend on PlusCal variables
     // define
     //   zy == y*(x+y)
@@ -1332,9 +1372,12 @@ module.exports = grammar({
     pcal_proc_decl: $ => seq(
       'procedure', field('name', $.identifier),
       '(',
-      optional(seq($.pcal_proc_var_decl, repeat(seq(',', $.pcal_proc_var_decl)))),
+      optional(seq(
+        field('parameter', $.pcal_proc_var_decl),
+        repeat(seq(',', field('parameter', $.pcal_proc_var_decl)))
+      )),
       ')',
-      optional($.pcal_proc_var_decls),
+      optional(field('variables', $.pcal_proc_var_decls)),
     ),
 
     // fair+ process bar in 1..10
@@ -1345,20 +1388,29 @@ module.exports = grammar({
     //   print(i);
     // end process
     pcal_p_process: $ => seq(
-      optional(seq('fair', optional('+'))),
+      optional(field('fairness', $.pcal_fairness)),
       'process', field('name', $.identifier),
-      choice('=', $.set_in),
-      $._expr,
+      $._pcal_process_bound,
       optional($.pcal_var_decls),
       alias($.pcal_p_algorithm_body, $.pcal_algorithm_body),
       'end', 'process', optional(';')
     ),
 
+    // Identifier of a single process, or the set of identifiers of a process set:
+    // = 1
+    // \in 1..N
+    _pcal_process_bound: $ => choice(
+      seq('=', field('id', $._expr)),
+      seq($.set_in, field('set', $._expr))
+    ),
+
+    // Weak (fair) or strong (fair+) fairness of a process
+    pcal_fairness: $ => seq('fair', optional('+')),
+
     pcal_c_process: $ => seq(
-      optional(seq('fair', optional('+'))),
+      optional(field('fairness', $.pcal_fairness)),
       'process', '(', field('name', $.identifier),
-      choice('=', $.set_in),
-      $._expr, ')',
+      $._pcal_process_bound, ')',
       optional($.pcal_var_decls),
       alias($.pcal_c_algorithm_body, $.pcal_algorithm_body),
       optional(';')
@@ -1378,8 +1430,8 @@ module.exports = grammar({
     // x \in 1..10
     // x = "x"
     pcal_var_decl: $ => seq(
-      $.identifier,
-      optional(seq(choice('=', $.set_in), $._expr)),
+      field('name', $.identifier),
+      optional(seq(choice('=', $.set_in), field('value', $._expr))),
     ),
 
     // Procedure local variables:
@@ -0,0 +1,12 @@
+; ; Intended for consumption by editors supporting tree-sitter code folding
+
+[
+  (block_comment)
+  (extramodular_text)
+  (module)
+  (non_terminal_proof)
+  (let_in)
+  (conj_list)
+  (disj_list)
+  (pcal_algorithm_body)
+] @fold
@@ -4,6 +4,7 @@
 ; ; In this file, captures defined earlier take precedence over captures defined later.
 
 ; TLA⁺ Keywords
+(assumption keyword: "AXIOM" @keyword.axiom)
 [
   "ACTION"
   "ASSUME"
@@ -34,8 +35,6 @@
   "LOCAL"
   "MODULE"
   "NEW"
-  "OBVIOUS"
-  "OMITTED"
   "ONLY"
   "OTHER"
   "PICK"
@@ -71,6 +70,8 @@
   (gets)
   (label_as)
   (maps_to)
+  (obvious)
+  (omitted)
   (set_in)
   (temporal_exists)
   (temporal_forall)
@@ -84,6 +85,7 @@
   "begin"
   "call"
   "define"
+  "do"
   "either"
   "else"
   "elsif"
@@ -99,11 +101,15 @@
   "variable"
   "variables"
   "when"
+  "while"
   "with"
   "then"
   (pcal_algorithm_start)
   (pcal_end_either)
   (pcal_end_if)
+  (pcal_end_while)
+  (pcal_end_with)
+  (pcal_fairness)
   (pcal_return)
   (pcal_skip)
   (pcal_process ("="))
@@ -140,7 +146,9 @@
 (pcal_var_decl (identifier) @variable.builtin)
 (pcal_with (identifier) @variable.parameter)
 ((".") . (identifier) @attribute)
+(except_update_record_field (identifier_ref) @attribute)
 (record_literal (identifier) @attribute)
+(record_value (_) (identifier_ref) @attribute)
 (set_of_records (identifier) @attribute)
 (variable_declaration (identifier) @variable.builtin)
 
@@ -168,6 +176,14 @@
 (pcal_process name: (identifier) @function)
 (recursive_declaration (identifier) @operator)
 (recursive_declaration (operator_declaration name: (_) @operator))
+[
+  (always)
+  (compose)
+  (eventually)
+  (leads_to)
+  (map_to)
+  (plus_arrow)
+] @operator
 
 ; Delimiters
 [
@@ -200,21 +216,27 @@
 (assumption name: (identifier) @constant)
 (pick_proof_step (identifier) @variable.parameter)
 (proof_step_id "<" @punctuation.bracket)
-(proof_step_id (level) @tag)
-(proof_step_id (name) @tag)
 (proof_step_id ">" @punctuation.bracket)
+(proof_step_id) @label
 (proof_step_ref "<" @punctuation.bracket)
-(proof_step_ref (level) @tag)
-(proof_step_ref (name) @tag)
 (proof_step_ref ">" @punctuation.bracket)
+(proof_step_ref) @label
 (take_proof_step (identifier) @variable.parameter)
 (theorem name: (identifier) @constant)
 
 ; Comments and tags
+; Annotations in comments, like Apalache types and PlusCal options
+((comment) @attribute
+  (#match? @attribute "^\\\\\\*\\s*@[A-Za-z]+:"))
+((block_comment_text) @attribute
+  (#match? @attribute "^\\s*@[A-Za-z]+:"))
+(pcal_options) @attribute
 (block_comment "(*" @comment)
 (block_comment "*)" @comment)
 (block_comment_text) @comment
 (comment) @comment
+(pcal_translation_start) @comment
+(pcal_translation_end) @comment
 (single_line) @comment
 (_ label: (identifier) @tag)
 (label name: (_) @tag)
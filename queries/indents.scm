@@ -0,0 +1,57 @@
+; ; Intended for consumption by nvim-treesitter and other editors which
+; ; support its indentation capture names:
+; ; https://github.com/nvim-treesitter/nvim-treesitter/blob/master/CONTRIBUTING.md#indents
+
+; Constructs whose continuation lines are indented
+[
+  (case)
+  (conj_list)
+  (disj_list)
+  (if_then_else)
+  (let_in)
+  (non_terminal_proof)
+  (operator_definition)
+  (function_definition)
+] @indent.begin
+
+; Keywords lining up with the start of their construct
+[
+  "IN"
+  "THEN"
+  "ELSE"
+  (case_box)
+] @indent.branch
+
+; Bracketed constructs spanning multiple lines align with their contents
+((record_literal "[" ) @indent.align
+  (#set! indent.open_delimiter "[")
+  (#set! indent.close_delimiter "]"))
+((set_of_records "[" ) @indent.align
+  (#set! indent.open_delimiter "[")
+  (#set! indent.close_delimiter "]"))
+((function_literal "[" ) @indent.align
+  (#set! indent.open_delimiter "[")
+  (#set! indent.close_delimiter "]"))
+((except "[" ) @indent.align
+  (#set! indent.open_delimiter "[")
+  (#set! indent.close_delimiter "]"))
+((finite_set_literal "{" ) @indent.align
+  (#set! indent.open_delimiter "{")
+  (#set! indent.close_delimiter "}"))
+((parentheses "(" ) @indent.align
+  (#set! indent.open_delimiter "(")
+  (#set! indent.close_delimiter ")"))
+
+; Closing delimiters return to the enclosing indentation
+[
+  "]"
+  "}"
+  ")"
+  (rangle_bracket)
+] @indent.end @indent.branch
+
+; Comments and extramodular text are left as written
+[
+  (block_comment)
+  (extramodular_text)
+] @indent.auto
@@ -0,0 +1,16 @@
+; ; Intended for consumption by GitHub and the tree-sitter highlight command
+; ; PlusCal algorithms inside block comments are parsed directly by this
+; ; grammar as (pcal_algorithm) nodes, so they are not injected here; only the
+; ; free-form text around them is handed to the comment or latex grammar.
+
+((comment) @injection.content
+  (#set! injection.language "comment"))
+
+; Block comment text with LaTeX math or commands, as in $x \in S$ or \emph{}
+((block_comment_text) @injection.content
+  (#match? @injection.content "\\$[^$]+\\$|\\\\[A-Za-z]+\\{")
+  (#set! injection.language "latex"))
+
+((block_comment_text) @injection.content
+  (#not-match? @injection.content "\\$[^$]+\\$|\\\\[A-Za-z]+\\{")
+  (#set! injection.language "comment"))
@@ -43,6 +43,7 @@
 (assume_prove (new (operator_declaration name: (_) @local.definition)))
 (assumption name: (identifier) @local.definition)
 (pick_proof_step (identifier) @local.definition)
+(proof_step_id) @local.definition
 (take_proof_step (identifier) @local.definition)
 (theorem name: (identifier) @local.definition)
 
@@ -61,6 +62,7 @@
 
 ; References
 (identifier_ref) @local.reference
+(proof_step_ref) @local.reference
 ((prefix_op_symbol) @local.reference)
 (bound_prefix_op symbol: (_) @local.reference)
 ((infix_op_symbol) @local.reference)
@@ -0,0 +1,20 @@
+; ; Intended for consumption by the tree-sitter tags command and crate
+
+; Modules
+(module name: (identifier) @name) @definition.module
+(module_definition name: (_) @name) @definition.module
+
+; Declarations
+(constant_declaration (identifier) @name) @definition.constant
+(constant_declaration (operator_declaration name: (_) @name)) @definition.constant
+(variable_declaration (identifier) @name) @definition.variable
+
+; Definitions
+(operator_definition name: (_) @name) @definition.operator
+(function_definition name: (identifier) @name) @definition.function
+
+; Theorems
+(theorem name: (identifier) @name) @definition.theorem
+
+; References
+(bound_op name: (identifier_ref) @name) @reference.call